@@ -1,126 +1,97 @@
-use chrono::{Local, Timelike};
+mod alarms;
+mod builder;
+mod calendar;
+mod converter;
+mod countdown;
+mod palette_editor;
+mod settings;
+mod stopwatch;
+mod world_clock;
+
+use self::{
+    alarms::{Alarm, AlarmDraft},
+    countdown::Countdown,
+    palette_editor::PaletteEditor,
+    settings::{HourFormat, MeridiemMarker, Settings, SubSecond, TransitionOptions},
+    stopwatch::Stopwatch,
+};
+use crate::{
+    decode::QuadrantStrokes,
+    glyph::{self, CompoundGlyph, Grouping, Orientation},
+    style::GlyphStyle,
+    theme::ClockTheme,
+    transition,
+    widget::{
+        meridiem_dot_room, paint_animated_segments, paint_glyph, paint_meridiem_dot,
+        paint_segments, MERIDIEM_DOT_WIDTH,
+    },
+    CistercianNumeral, CompoundNumeral,
+};
+use chrono::Timelike;
 use core::time::Duration;
 use egui::{
-    epaint::Shadow,
-    scroll_area::ScrollBarVisibility,
-    style::{HandleShape, NumericColorSpace, Selection, TextCursorStyle, Widgets},
-    vec2, Color32, CornerRadius,
-    FontFamily::Proportional,
-    FontId, Painter, Pos2, ScrollArea, Sense, Stroke,
-    TextStyle::{self, Body, Button, Heading, Monospace, Name, Small},
-    Ui, Vec2, Visuals,
+    scroll_area::ScrollBarVisibility, vec2, Align, Id, Layout, RichText, ScrollArea, Sense, Ui,
+    Vec2,
 };
 
-fn dark_mode_override() -> Visuals {
-    Visuals {
-        dark_mode: true,
-        override_text_color: Some(Color32::from_gray(252)),
-        widgets: Widgets::default(),
-        selection: Selection::default(),
-        hyperlink_color: Color32::from_rgb(90, 170, 255),
-        faint_bg_color: Color32::from_additive_luminance(5), // visible, but barely so
-        extreme_bg_color: Color32::from_gray(10),            // e.g. TextEdit background
-        code_bg_color: Color32::from_gray(64),
-        warn_fg_color: Color32::from_rgb(255, 143, 0), // orange
-        error_fg_color: Color32::from_rgb(255, 0, 0),  // red
-
-        window_corner_radius: CornerRadius::same(6),
-        window_shadow: Shadow {
-            offset: [10, 20],
-            blur: 15,
-            spread: 0,
-            color: Color32::from_black_alpha(96),
-        },
-        window_fill: Color32::from_rgb(23, 18, 25),
-        window_stroke: Stroke::new(1.0, Color32::from_gray(60)),
-        window_highlight_topmost: true,
-
-        menu_corner_radius: CornerRadius::same(6),
-
-        panel_fill: Color32::from_rgb(23, 18, 25),
-
-        popup_shadow: Shadow {
-            offset: [6, 10],
-            blur: 8,
-            spread: 0,
-            color: Color32::from_black_alpha(96),
-        },
-        resize_corner_size: 12.0,
-        text_cursor: Default::default(),
-        clip_rect_margin: 3.0, // should be at least half the size of the widest frame stroke + max WidgetVisuals::expansion
-        button_frame: true,
-        collapsing_header_frame: false,
-        indent_has_left_vline: true,
-
-        striped: false,
-
-        slider_trailing_fill: false,
-
-        handle_shape: HandleShape::Rect { aspect_ratio: 1.0 },
-
-        interact_cursor: None,
-
-        image_loading_spinners: true,
-
-        numeric_color_space: NumericColorSpace::GammaByte,
-    }
-}
-
-pub fn light_mode_override() -> Visuals {
-    Visuals {
-        dark_mode: false,
-        override_text_color: Some(Color32::from_rgb(4, 3, 15)),
-        widgets: Widgets::light(),
-        selection: Selection::default(),
-        hyperlink_color: Color32::from_rgb(0, 155, 255),
-        faint_bg_color: Color32::from_additive_luminance(5), // visible, but barely so
-        extreme_bg_color: Color32::from_gray(255),           // e.g. TextEdit background
-        code_bg_color: Color32::from_gray(230),
-        warn_fg_color: Color32::from_rgb(255, 100, 0), // slightly orange red. it's difficult to find a warning color that pops on bright background.
-        error_fg_color: Color32::from_rgb(255, 0, 0),  // red
-
-        window_shadow: Shadow {
-            offset: [10, 20],
-            blur: 15,
-            spread: 0,
-            color: Color32::from_black_alpha(25),
-        },
-        window_fill: Color32::from_gray(255),
-        window_stroke: Stroke::new(1.0, Color32::from_gray(190)),
-
-        panel_fill: Color32::from_gray(255),
-
-        popup_shadow: Shadow {
-            offset: [6, 10],
-            blur: 8,
-            spread: 0,
-            color: Color32::from_black_alpha(25),
-        },
-        text_cursor: TextCursorStyle {
-            stroke: Stroke::new(2.0, Color32::from_rgb(0, 83, 125)),
-            ..Default::default()
-        },
-        ..Visuals::dark()
-    }
-}
-
 /// We derive Deserialize/Serialize so we can persist app state on shutdown.
 #[derive(serde::Deserialize, serde::Serialize)]
 #[serde(default)] // if we add new fields, give them default values when deserializing old state
 pub struct CistercianClockApp {
-    // Example stuff:
-    label: String,
+    #[serde(default = "Settings::unversioned")]
+    settings: Settings,
+
+    /// Strokes the user has picked in the glyph builder, in units to thousands order.
+    builder_strokes: [QuadrantStrokes; 4],
+
+    /// Last text entered in the number converter.
+    converter_input: String,
+    converter_scale: f32,
+    converter_grouping: Grouping,
+
+    /// Zone picked in the world clock, waiting to be added.
+    #[serde(skip)]
+    new_world_clock_zone: Option<String>,
+
+    #[serde(skip)]
+    stopwatch: Stopwatch,
 
-    #[serde(skip)] // This how you opt-out of serialization of a field
-    value: f32,
+    #[serde(skip)]
+    countdown: Countdown,
+
+    /// Kept outside [`Settings`], so resetting the settings leaves them alone.
+    alarms: Vec<Alarm>,
+
+    /// Indices into `alarms` of the alarms which have gone off and not been dismissed.
+    #[serde(skip)]
+    ringing_alarms: Vec<usize>,
+
+    #[serde(skip)]
+    alarm_draft: AlarmDraft,
+
+    #[serde(skip)]
+    palette_editor: PaletteEditor,
+
+    #[serde(skip)]
+    theme: ClockTheme,
 }
 
 impl Default for CistercianClockApp {
     fn default() -> Self {
         Self {
-            // Example stuff:
-            label: "Hello World!".to_owned(),
-            value: 2.7,
+            settings: Settings::default(),
+            builder_strokes: Default::default(),
+            converter_input: "1984".to_owned(),
+            converter_scale: 4.0,
+            converter_grouping: Grouping::default(),
+            new_world_clock_zone: None,
+            stopwatch: Stopwatch::default(),
+            countdown: Countdown::default(),
+            alarms: Vec::new(),
+            ringing_alarms: Vec::new(),
+            alarm_draft: AlarmDraft::default(),
+            palette_editor: PaletteEditor::default(),
+            theme: ClockTheme::default(),
         }
     }
 }
@@ -128,378 +99,301 @@ impl Default for CistercianClockApp {
 impl CistercianClockApp {
     /// Called once before the first frame.
     pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
-        // This is also where you can customize the look and feel of egui using
-        // `cc.egui_ctx.set_visuals` and `cc.egui_ctx.set_fonts`.
+        Self::with_theme(cc, ClockTheme::default())
+    }
+
+    /// Like [`Self::new`], with `theme` in place of the default look.
+    pub fn with_theme(cc: &eframe::CreationContext<'_>, theme: ClockTheme) -> Self {
+        theme.apply(&cc.egui_ctx);
 
         // Load previous app state (if any).
         // Note that you must enable the `persistence` feature for this to work.
-        if let Some(storage) = cc.storage {
-            return eframe::get_value(storage, eframe::APP_KEY).unwrap_or_default();
-        }
+        let mut app = cc
+            .storage
+            .and_then(|storage| storage.get_string(eframe::APP_KEY))
+            .map_or_else(Self::default, |saved| Self::from_saved(&saved));
+        app.settings = app.settings.migrate().sanitise();
+        app.theme = theme;
+        app
+    }
 
-        Default::default()
+    /// App state from the RON `saved` by [`eframe::App::save`]. State which cannot be read, such
+    /// as a hand-edited colour which is not valid hex, is reported in the log and replaced with
+    /// the defaults.
+    fn from_saved(saved: &str) -> Self {
+        ron::from_str(saved).unwrap_or_else(|error| {
+            log::warn!("Saved app state could not be read, so using the defaults: {error}");
+            Self::default()
+        })
     }
 }
 
-struct Colours {
-    colour_0: Color32,
-    colour_1: Color32,
-    colour_2: Color32,
-    colour_3: Color32,
-    colour_4: Color32,
-    colour_6: Color32,
+/// Numeral for the last four digits of `value`, for counts such as minutes which can pass 9,999.
+fn wrapping_numeral(value: u64) -> CistercianNumeral {
+    CistercianNumeral::try_from((value % 10_000) as u32)
+        .expect("Value below 10,000 should fit in a single numeral")
 }
 
-fn paint_unit_number(
-    painter: &mut Painter,
-    centre: Pos2,
-    scale: f32,
-    colours: &Colours,
-    number: u32,
+fn paint_number(
+    ui: &mut Ui,
+    style: &GlyphStyle,
+    numeral: CistercianNumeral,
+    scale: Option<f32>,
+    show_arabic_numeral: Option<bool>,
 ) {
-    let width = scale * (34.0 / 2.0 - 1.0);
-    let stroke_width = if scale < 2.0 { 2.0 } else { scale * 1.0 };
-    let Colours {
-        colour_0,
-        colour_1,
-        colour_2,
-        colour_3,
-        colour_4,
-        colour_6,
-    } = colours;
-    let stroke = Stroke::new(stroke_width, *colour_0);
-    painter.line_segment(
-        [centre - vec2(0.0, width), centre + vec2(0.0, width)],
-        stroke,
-    );
-
-    if number == 1 || number == 5 || number == 7 || number == 9 {
-        let stroke = Stroke::new(stroke_width, *colour_1);
-        painter.line_segment(
-            [
-                centre - vec2(0.0, width),
-                centre + vec2(scale * 10.0, -width),
-            ],
-            stroke,
-        );
+    let scale = scale.unwrap_or(1.0);
+    if let Some(true) = show_arabic_numeral {
+        ui.label(format!("{numeral:#}"));
     }
 
-    if number == 2 || number == 8 || number == 9 {
-        let stroke = Stroke::new(stroke_width, *colour_2);
-        painter.line_segment(
-            [
-                centre - vec2(0.0, width - scale * 10.0),
-                centre + vec2(scale * 10.0, -width + (scale * 10.0)),
-            ],
-            stroke,
-        );
-    }
+    let size = Vec2::splat(scale * style.cell_size(scale));
+    let (response, painter) = ui.allocate_painter(size, Sense::hover());
+    let rect = response.rect;
+    let c = rect.center();
 
-    if number == 3 {
-        let stroke = Stroke::new(stroke_width, *colour_3);
-        painter.line_segment(
-            [
-                centre - vec2(0.0, width),
-                centre + vec2(scale * 10.0, -width + scale * 10.0),
-            ],
-            stroke,
-        );
-    }
+    paint_glyph(&painter, c, scale, style, numeral);
+}
 
-    if number == 4 || number == 5 {
-        let stroke = Stroke::new(stroke_width, *colour_4);
-        painter.line_segment(
-            [
-                centre - vec2(0.0, width - scale * 10.0),
-                centre + vec2(scale * 10.0, -width),
-            ],
-            stroke,
-        );
+/// Draws `numeral` like [`paint_number`] but, when transitions are on, animates the change from
+/// the value last drawn with the same `id`.
+fn paint_animated_number(
+    ui: &mut Ui,
+    style: &GlyphStyle,
+    id: Id,
+    numeral: CistercianNumeral,
+    scale: f32,
+    options: &TransitionOptions,
+) {
+    if !options.enabled {
+        paint_number(ui, style, numeral, Some(scale), None);
+        return;
     }
 
-    if number > 5 {
-        let stroke = Stroke::new(stroke_width, *colour_6);
-        painter.line_segment(
-            [
-                centre + vec2(scale * 10.0, -width),
-                centre + vec2(scale * 10.0, -width + scale * 10.0),
-            ],
-            stroke,
-        );
-    }
+    // Each change of value bumps the generation, which egui then animates towards.
+    let (from, to, generation) = ui.data_mut(|data| {
+        let state = data.get_temp_mut_or_insert_with(id, || (numeral, numeral, 0_u32));
+        if state.1 != numeral {
+            *state = (state.1, numeral, state.2.wrapping_add(1));
+        }
+        *state
+    });
+    let animated =
+        ui.ctx()
+            .animate_value_with_time(id, generation as f32, options.duration_seconds);
+    let progress = 1.0 - (generation as f32 - animated).clamp(0.0, 1.0);
+
+    let size = Vec2::splat(scale * style.cell_size(scale));
+    let (response, painter) = ui.allocate_painter(size, Sense::hover());
+    let segments =
+        transition::transition_segments(from, to, options.style, style.orientation, progress);
+    paint_animated_segments(&painter, response.rect.center(), scale, style, &segments);
 }
 
-fn paint_tens_number(
-    painter: &mut Painter,
-    centre: Pos2,
+/// Narrowest space, in points, the clock glyphs are laid out side by side in. Below it they
+/// stack, one above the other.
+const COMPACT_WIDTH: f32 = 480.0;
+
+/// Share of the panel height the clock may take while other panels are shown below it.
+const CLOCK_HEIGHT_SHARE: f32 = 0.5;
+
+/// Space under the clock, in points.
+const CLOCK_BOTTOM_SPACE: f32 = 20.0;
+
+/// Room a meridiem caption takes, in points.
+const MERIDIEM_CAPTION_WIDTH: f32 = 30.0;
+
+/// Scale and arrangement of the main clock glyphs.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct ClockLayout {
     scale: f32,
-    colours: &Colours,
-    number: u32,
-) {
-    let width = scale * (34.0 / 2.0 - 1.0);
-    let stroke_width = if scale < 2.0 { 2.0 } else { scale * 1.0 };
-    let Colours {
-        colour_1,
-        colour_2,
-        colour_3,
-        colour_4,
-        colour_6,
-        ..
-    } = colours;
-
-    if number == 1 || number == 5 || number == 7 || number == 9 {
-        let stroke = Stroke::new(stroke_width, *colour_1);
-        painter.line_segment(
-            [
-                centre - vec2(scale * 10.0, width),
-                centre + vec2(0.0, -width),
-            ],
-            stroke,
-        );
-    }
-    if number == 2 || number == 8 || number == 9 {
-        let stroke = Stroke::new(stroke_width, *colour_2);
-        painter.line_segment(
-            [
-                centre - vec2(scale * 10.0, width - scale * 10.0),
-                centre + vec2(0.0, -width + scale * 10.0),
-            ],
-            stroke,
-        );
-    }
-    if number == 3 {
-        let stroke = Stroke::new(stroke_width, *colour_3);
-        painter.line_segment(
-            [
-                centre - vec2(scale * 10.0, width - scale * 10.0),
-                centre + vec2(0.0, -width),
-            ],
-            stroke,
-        );
-    }
-    if number == 4 || number == 5 {
-        let stroke = Stroke::new(stroke_width, *colour_4);
-        painter.line_segment(
-            [
-                centre - vec2(scale * 10.0, width),
-                centre + vec2(0.0, -width + scale * 10.0),
-            ],
-            stroke,
-        );
-    }
-    if number > 5 {
-        let stroke = Stroke::new(stroke_width, *colour_6);
-        painter.line_segment(
-            [
-                centre + vec2(-scale * 10.0, -width),
-                centre + vec2(-scale * 10.0, -width + scale * 10.0),
-            ],
-            stroke,
-        );
+
+    /// Glyphs one above the other, for narrow windows. The meridiem marker stays with the
+    /// hours-minutes glyph.
+    stacked: bool,
+}
+
+impl ClockLayout {
+    /// Layout for `glyphs` clock glyphs, the first with a meridiem marker of `marker` points
+    /// after it plus `marker_units` glyph units across and down, in `available` points. `scale`
+    /// is used as it is unless `fit` is set, when the glyphs, in cells sized for `style`, grow or
+    /// shrink to fill the space.
+    fn new(
+        available: Vec2,
+        spacing: f32,
+        glyphs: usize,
+        (marker, marker_units): (f32, Vec2),
+        style: &GlyphStyle,
+        scale: f32,
+        fit: bool,
+    ) -> Self {
+        let stacked = available.x < COMPACT_WIDTH;
+        if !fit {
+            return Self { scale, stacked };
+        }
+
+        let count = glyphs as f32;
+        let gaps = spacing * (count - 1.0);
+        let gap_across = if marker > 0.0 || marker_units.x > 0.0 {
+            spacing
+        } else {
+            0.0
+        };
+        let gap_down = if marker_units.y > 0.0 { spacing } else { 0.0 };
+        let (available, cells) = if stacked {
+            (
+                (
+                    available.x - marker - gap_across,
+                    available.y - gaps - gap_down,
+                ),
+                (1.0, count),
+            )
+        } else {
+            (
+                (
+                    available.x - gaps - marker - gap_across,
+                    available.y - gap_down,
+                ),
+                (count, 1.0),
+            )
+        };
+        let scale = style.fit_scale(available, cells, (marker_units.x, marker_units.y));
+        Self {
+            scale: scale.max(*Settings::GLYPH_SCALE_RANGE.start()),
+            stacked,
+        }
     }
 }
 
-fn paint_hundreds_number(
-    painter: &mut Painter,
-    centre: Pos2,
+/// Marks an hours-minutes glyph as before or after noon, in 12-hour mode.
+fn paint_meridiem_marker(
+    ui: &mut Ui,
+    style: &GlyphStyle,
+    marker: MeridiemMarker,
+    is_pm: bool,
     scale: f32,
-    colours: &Colours,
-    number: u32,
 ) {
-    let width = scale * (34.0 / 2.0 - 1.0);
-    let stroke_width = if scale < 2.0 { 2.0 } else { scale * 1.0 };
-    let Colours {
-        colour_1,
-        colour_2,
-        colour_3,
-        colour_4,
-        colour_6,
-        ..
-    } = colours;
-
-    if number == 1 || number == 5 || number == 7 || number == 9 {
-        let stroke = Stroke::new(stroke_width, *colour_1);
-        painter.line_segment(
-            [
-                centre + vec2(0.0, width),
-                centre + vec2(scale * 10.0, width),
-            ],
-            stroke,
-        );
-    }
-    if number == 2 || number == 8 || number == 9 {
-        let stroke = Stroke::new(stroke_width, *colour_2);
-        painter.line_segment(
-            [
-                centre + vec2(0.0, width - scale * 10.0),
-                centre + vec2(scale * 10.0, width - scale * 10.0),
-            ],
-            stroke,
-        );
-    }
-    if number == 3 {
-        let stroke = Stroke::new(stroke_width, *colour_3);
-        painter.line_segment(
-            [
-                centre + vec2(0.0, width),
-                centre + vec2(scale * 10.0, width - scale * 10.0),
-            ],
-            stroke,
-        );
-    }
-    if number == 4 || number == 5 {
-        let stroke = Stroke::new(stroke_width, *colour_4);
-        painter.line_segment(
-            [
-                centre + vec2(0.0, width - scale * 10.0),
-                centre + vec2(scale * 10.0, width),
-            ],
-            stroke,
-        );
-    }
-    if number > 5 {
-        let stroke = Stroke::new(stroke_width, *colour_6);
-        painter.line_segment(
-            [
-                centre + vec2(scale * 10.0, width - scale * 10.0),
-                centre + vec2(scale * 10.0, width),
-            ],
-            stroke,
-        );
+    match marker {
+        MeridiemMarker::Caption => {
+            ui.label(if is_pm { "PM" } else { "AM" });
+        }
+        MeridiemMarker::Dot => {
+            let size = match style.orientation {
+                Orientation::Vertical => {
+                    vec2(scale * MERIDIEM_DOT_WIDTH, scale * style.cell_size(scale))
+                }
+                Orientation::Horizontal => {
+                    vec2(scale * style.cell_size(scale), scale * MERIDIEM_DOT_WIDTH)
+                }
+            };
+            let (response, painter) = ui.allocate_painter(size, Sense::hover());
+            paint_meridiem_dot(&painter, response.rect.center(), scale, style, is_pm);
+        }
     }
 }
 
-fn paint_thousands_number(
-    painter: &mut Painter,
-    centre: Pos2,
+fn paint_compound_number(
+    ui: &mut Ui,
+    style: &GlyphStyle,
+    number: CompoundNumeral,
+    grouping: Grouping,
     scale: f32,
-    colours: &Colours,
-    number: u32,
+    show_arabic_numeral: bool,
 ) {
-    let width = scale * (34.0 / 2.0 - 1.0);
-    let stroke_width = if scale < 2.0 { 2.0 } else { scale * 1.0 };
-    let Colours {
-        colour_1,
-        colour_2,
-        colour_3,
-        colour_4,
-        colour_6,
-        ..
-    } = colours;
-
-    if number == 1 || number == 5 || number == 7 || number == 9 {
-        let stroke = Stroke::new(stroke_width, *colour_1);
-        painter.line_segment(
-            [
-                centre + vec2(-scale * 10.0, width),
-                centre + vec2(0.0, width),
-            ],
-            stroke,
-        );
-    }
-    if number == 2 || number == 8 || number == 9 {
-        let stroke = Stroke::new(stroke_width, *colour_2);
-        painter.line_segment(
-            [
-                centre + vec2(-scale * 10.0, width - scale * 10.0),
-                centre + vec2(0.0, width - scale * 10.0),
-            ],
-            stroke,
-        );
-    }
-    if number == 3 {
-        let stroke = Stroke::new(stroke_width, *colour_3);
-        painter.line_segment(
-            [
-                centre + vec2(-scale * 10.0, width - scale * 10.0),
-                centre + vec2(0.0, width),
-            ],
-            stroke,
-        );
-    }
-    if number == 4 || number == 5 {
-        let stroke = Stroke::new(stroke_width, *colour_4);
-        painter.line_segment(
-            [
-                centre + vec2(-scale * 10.0, width),
-                centre + vec2(0.0, width - scale * 10.0),
-            ],
-            stroke,
-        );
-    }
-    if number > 5 {
-        let stroke = Stroke::new(stroke_width, *colour_6);
-        painter.line_segment(
-            [
-                centre + vec2(-scale * 10.0, width - scale * 10.0),
-                centre + vec2(-scale * 10.0, width),
-            ],
-            stroke,
-        );
+    if show_arabic_numeral {
+        ui.label(format!("{number:#}"));
     }
+
+    let CompoundGlyph {
+        segments,
+        width,
+        height,
+    } = glyph::compound_glyph(number, grouping, style.orientation);
+    let margin = 2.0 * style.margin(scale);
+    let (response, painter) = ui.allocate_painter(
+        scale * vec2(width + margin, height + margin),
+        Sense::hover(),
+    );
+    paint_segments(&painter, response.rect.center(), scale, style, &segments);
 }
 
-fn paint_number(
+/// Reference chart entry for `numeral`, drawn in both orientations side by side when
+/// `both_orientations` is set.
+fn paint_chart_entry(
     ui: &mut Ui,
-    colours: &Colours,
-    number: u32,
-    scale: Option<f32>,
-    show_arabic_numeral: Option<bool>,
+    style: &GlyphStyle,
+    numeral: CistercianNumeral,
+    captions: bool,
+    both_orientations: bool,
 ) {
-    let scale = scale.unwrap_or(1.0);
-    assert!((0..=9_999).contains(&number));
-    if let Some(true) = show_arabic_numeral {
-        match number {
-            0..=999 => ui.label(number.to_string()),
-            _ => ui.label(format!("{},{:003}", number / 1000, number % 1000)),
-        };
-    }
-
-    let size = Vec2::splat(scale * 34.0);
-    let (response, mut painter) = ui.allocate_painter(size, Sense::hover());
-    let rect = response.rect;
-    let c = rect.center();
+    ui.horizontal_top(|ui| {
+        if !both_orientations {
+            paint_number(ui, style, numeral, None, Some(captions));
+            return;
+        }
+        if captions {
+            ui.label(format!("{numeral:#}"));
+        }
+        for orientation in Orientation::ALL {
+            let style = GlyphStyle {
+                orientation,
+                ..*style
+            };
+            paint_number(ui, &style, numeral, None, None);
+        }
+    });
+}
 
-    let unit = number % 10;
-    paint_unit_number(&mut painter, c, scale, colours, unit);
-    if number > 9 {
-        let tens = (number % 100) / 10;
-        paint_tens_number(&mut painter, c, scale, colours, tens);
-    }
-    if number > 99 {
-        let hundreds = (number % 1_000) / 100;
-        paint_hundreds_number(&mut painter, c, scale, colours, hundreds);
-    }
-    if number > 999 {
-        let thousands = (number % 10_000) / 1_000;
-        paint_thousands_number(&mut painter, c, scale, colours, thousands);
-    }
+fn paint_number_row(
+    ui: &mut Ui,
+    style: &GlyphStyle,
+    numbers: impl Iterator<Item = u32>,
+    captions: bool,
+    both_orientations: bool,
+) {
+    ui.horizontal_wrapped(|ui| {
+        for number in numbers {
+            let Ok(numeral) = CistercianNumeral::try_from(number) else {
+                continue;
+            };
+            paint_chart_entry(ui, style, numeral, captions, both_orientations);
+        }
+    });
 }
 
-const DARK_CISTERCIAN_NUMERAL_COLOURS: Colours = Colours {
-    colour_0: Color32::from_gray(242),
-    colour_1: Color32::from_rgb(58, 134, 255),
-    colour_2: Color32::from_rgb(251, 86, 7),
-    colour_3: Color32::from_rgb(162, 106, 241),
-    colour_4: Color32::from_rgb(255, 0, 110),
-    colour_6: Color32::from_rgb(255, 190, 11),
-};
+/// Chart of 0–99, followed by examples of hundreds and thousands.
+fn reference_chart(ui: &mut Ui, style: &GlyphStyle, captions: bool, both_orientations: bool) {
+    ui.heading("Cistercian Numbers");
+    ui.add_space(30.0);
+    paint_number_row(ui, style, 0..10, captions, both_orientations);
+    ui.add_space(30.0);
+    for tens in 1..10 {
+        paint_number_row(
+            ui,
+            style,
+            10 * tens..(tens + 1) * 10,
+            captions,
+            both_orientations,
+        );
+        ui.add_space(15.0);
+    }
 
-const LIGHT_CISTERCIAN_NUMERAL_COLOURS: Colours = Colours {
-    colour_0: Color32::from_rgb(4, 3, 15),
-    colour_1: Color32::from_rgb(93, 93, 91),
-    colour_2: Color32::from_rgb(0, 122, 94),
-    colour_3: Color32::from_rgb(27, 42, 65),
-    colour_4: Color32::from_rgb(150, 2, 0),
-    colour_6: Color32::from_rgb(0, 122, 163),
-};
+    ui.add_space(30.0);
+    paint_number_row(
+        ui,
+        style,
+        (1..5).map(|number| number * 100),
+        captions,
+        both_orientations,
+    );
 
-fn paint_number_row(ui: &mut Ui, colours: &Colours, start: u32, end: u32) {
-    ui.horizontal(|ui| {
-        for number in start..end {
-            ui.horizontal_top(|ui| paint_number(ui, colours, number, None, Some(true)));
-        }
-    });
+    ui.add_space(30.0);
+    paint_number_row(
+        ui,
+        style,
+        (1..5).map(|number| number * 1_000),
+        captions,
+        both_orientations,
+    );
 }
 
 impl eframe::App for CistercianClockApp {
@@ -514,12 +408,6 @@ impl eframe::App for CistercianClockApp {
         // For inspiration and more examples, go to https://emilk.github.io/egui
 
         egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
-            if ui.visuals().dark_mode {
-                ctx.set_visuals(dark_mode_override());
-            } else {
-                ctx.set_visuals(light_mode_override());
-            };
-
             // The top panel is often a good place for a menu bar:
 
             egui::menu::bar(ui, |ui| {
@@ -534,79 +422,211 @@ impl eframe::App for CistercianClockApp {
                     ui.add_space(16.0);
                 }
 
+                ui.menu_button("View", |ui| self.settings.panels.ui(ui));
+                ui.menu_button("Settings", |ui| self.settings.ui(ui));
+                ui.add_space(16.0);
+
                 egui::widgets::global_theme_preference_switch(ui);
             });
         });
 
-        let mut style = (*ctx.style()).clone();
-        style.text_styles = [
-            (Heading, FontId::new(30.0, Proportional)),
-            (Name("clock".into()), FontId::new(64.0, Proportional)),
-            (Body, FontId::new(18.0, Proportional)),
-            (Monospace, FontId::new(14.0, Proportional)),
-            (Button, FontId::new(14.0, Proportional)),
-            (Small, FontId::new(10.0, Proportional)),
-        ]
-        .into();
-        ctx.set_style(style);
+        let style = self.settings.glyph_style(ctx.style().visuals.dark_mode);
+        self.countdown_tick(ctx);
+        self.alarms_tick(ctx);
+        self.ringing_alarms_ui(ctx, &style);
+
+        if self.settings.panels.glyph_builder {
+            egui::SidePanel::right("glyph_builder")
+                .resizable(false)
+                .show(ctx, |ui| self.glyph_builder_ui(ui, &style));
+        }
 
         egui::CentralPanel::default().show(ctx, |ui| {
-            let colours = if ui.visuals().dark_mode {
-                DARK_CISTERCIAN_NUMERAL_COLOURS
+            let Settings {
+                ref time_zone,
+                hour_format,
+                meridiem_marker,
+                show_seconds,
+                sub_second,
+                glyph_scale,
+                fit_clock_to_window,
+                show_arabic_captions,
+                chart_both_orientations,
+                ref transition,
+                ref panels,
+                ..
+            } = self.settings;
+            if panels.clock && sub_second != SubSecond::Off {
+                // Animate the fraction glyph, but only while it is shown.
+                ui.ctx().request_repaint();
             } else {
-                LIGHT_CISTERCIAN_NUMERAL_COLOURS
-            };
-            ui.ctx().request_repaint_after(Duration::new(1, 0));
+                ui.ctx().request_repaint_after(Duration::new(1, 0));
+            }
             // The central panel the region left after adding TopPanel's and SidePanel's
             ui.heading("Cistercian Time");
             ui.add_space(30.0);
 
-            let now = Local::now();
-            let hours_minutes: u32 = now.hour() * 100 + now.minute();
-            let seconds: u32 = now.second();
-            let time = now.format("%H:%M %S").to_string();
-            ui.horizontal(|ui| {
-                paint_number(ui, &colours, hours_minutes, Some(4.0), None);
-                paint_number(ui, &colours, seconds, Some(4.0), None);
-            });
-            ui.add_space(20.0);
-            ui.horizontal(|ui| {
-                ui.style_mut().override_text_style = Some(TextStyle::Name("clock".into()));
-                ui.label(time)
-            });
-            ui.add_space(20.0);
+            let now = world_clock::now_in_zone(time_zone.as_deref());
+            if let Some(zone) = time_zone {
+                match now {
+                    Some(now) => {
+                        ui.label(format!(
+                            "{zone} ({})",
+                            world_clock::utc_offset_label(now.offset())
+                        ));
+                    }
+                    None => {
+                        ui.horizontal(|ui| {
+                            ui.label(zone);
+                            world_clock::unknown_zone_label(ui);
+                        });
+                    }
+                }
+                ui.add_space(10.0);
+            }
+            // Show nothing for an unknown zone, rather than the wrong time.
+            if let Some(now) = now {
+                let (hours_minutes, seconds) =
+                    glyph::time_numerals(now.time(), hour_format == HourFormat::TwelveHour);
+                if panels.clock {
+                    let fraction = sub_second.value(now.timestamp_subsec_nanos());
+                    let glyphs = 1 + usize::from(show_seconds) + usize::from(fraction.is_some());
+                    let marker = match (hour_format, meridiem_marker) {
+                        (HourFormat::TwentyFourHour, _) => (0.0, Vec2::ZERO),
+                        (HourFormat::TwelveHour, MeridiemMarker::Caption) => {
+                            (MERIDIEM_CAPTION_WIDTH, Vec2::ZERO)
+                        }
+                        (HourFormat::TwelveHour, MeridiemMarker::Dot) => {
+                            (0.0, meridiem_dot_room(style.orientation))
+                        }
+                    };
+                    let mut available = ui.available_size() - vec2(0.0, CLOCK_BOTTOM_SPACE);
+                    if !panels.clock_only() {
+                        available.y *= CLOCK_HEIGHT_SHARE;
+                    }
+                    let ClockLayout { scale, stacked } = ClockLayout::new(
+                        available,
+                        ui.spacing().item_spacing.x,
+                        glyphs,
+                        marker,
+                        &style,
+                        glyph_scale,
+                        fit_clock_to_window,
+                    );
+                    let layout = if stacked {
+                        Layout::top_down(Align::Min)
+                    } else {
+                        Layout::left_to_right(Align::Min)
+                    };
+                    ui.with_layout(layout, |ui| {
+                        let hours_minutes_ui = |ui: &mut Ui| {
+                            paint_animated_number(
+                                ui,
+                                &style,
+                                Id::new("clock_hours_minutes"),
+                                hours_minutes,
+                                scale,
+                                transition,
+                            );
+                            if hour_format == HourFormat::TwelveHour {
+                                let is_pm = now.hour() >= 12;
+                                paint_meridiem_marker(ui, &style, meridiem_marker, is_pm, scale);
+                            }
+                        };
+                        // A dot for a horizontal stem sits in a row under the glyph, not beside it.
+                        if meridiem_marker == MeridiemMarker::Dot
+                            && style.orientation == Orientation::Horizontal
+                        {
+                            ui.vertical(hours_minutes_ui);
+                        } else {
+                            ui.horizontal(hours_minutes_ui);
+                        }
+                        if show_seconds {
+                            paint_animated_number(
+                                ui,
+                                &style,
+                                Id::new("clock_seconds"),
+                                seconds,
+                                scale,
+                                transition,
+                            );
+                        }
+                        if let Some(fraction) = fraction {
+                            let fraction = CistercianNumeral::try_from(fraction)
+                                .expect("Fraction of a second should fit in a single numeral");
+                            paint_number(ui, &style, fraction, Some(scale), None);
+                        }
+                    });
+                    ui.add_space(CLOCK_BOTTOM_SPACE);
+                }
+                if panels.date {
+                    calendar::date_row(ui, &style, &now, &self.settings);
+                    ui.add_space(20.0);
+                }
+                if panels.digital_time {
+                    let time = match (hour_format, show_seconds) {
+                        (HourFormat::TwentyFourHour, true) => now.format("%H:%M %S"),
+                        (HourFormat::TwentyFourHour, false) => now.format("%H:%M"),
+                        (HourFormat::TwelveHour, true) => now.format("%-I:%M %S %p"),
+                        (HourFormat::TwelveHour, false) => now.format("%-I:%M %p"),
+                    }
+                    .to_string();
+                    ui.label(RichText::new(time).font(self.theme.clock_font.clone()));
+                    ui.add_space(20.0);
+                }
+            }
 
             ui.separator();
+            let show_world_clock = panels.world_clock;
+            let show_stopwatch = panels.stopwatch;
+            let show_countdown = panels.countdown;
+            let show_alarms = panels.alarms;
+            let show_converter = panels.converter;
+            let show_palette_editor = panels.palette_editor;
+            let show_reference_chart = panels.reference_chart;
             ScrollArea::vertical()
                 .auto_shrink(false)
                 .scroll_bar_visibility(ScrollBarVisibility::default())
                 .show(ui, |ui| {
-                    ui.heading("Cistercian Numbers");
-                    ui.add_space(30.0);
-                    paint_number_row(ui, &colours, 0, 10);
-                    ui.add_space(30.0);
-                    for tens in 1..10 {
-                        paint_number_row(ui, &colours, 10 * tens, (tens + 1) * 10);
-                        ui.add_space(15.0);
+                    if show_world_clock {
+                        self.world_clock_ui(ui, &style);
+                        ui.add_space(30.0);
+                        ui.separator();
                     }
 
-                    ui.add_space(30.0);
-                    ui.horizontal(|ui| {
-                        for number in 1..5 {
-                            ui.horizontal_top(|ui| {
-                                paint_number(ui, &colours, number * 100, None, Some(true));
-                            });
-                        }
-                    });
+                    if show_stopwatch {
+                        self.stopwatch_ui(ui, &style);
+                        ui.add_space(30.0);
+                        ui.separator();
+                    }
 
-                    ui.add_space(30.0);
-                    ui.horizontal(|ui| {
-                        for number in 1..5 {
-                            ui.horizontal_top(|ui| {
-                                paint_number(ui, &colours, number * 1_000, None, Some(true));
-                            });
-                        }
-                    });
+                    if show_countdown {
+                        self.countdown_ui(ui, &style);
+                        ui.add_space(30.0);
+                        ui.separator();
+                    }
+
+                    if show_alarms {
+                        self.alarms_ui(ui, &style);
+                        ui.add_space(30.0);
+                        ui.separator();
+                    }
+
+                    if show_converter {
+                        self.converter_ui(ui, &style);
+                        ui.add_space(30.0);
+                        ui.separator();
+                    }
+
+                    if show_palette_editor {
+                        self.palette_editor_ui(ui, &style);
+                        ui.add_space(30.0);
+                        ui.separator();
+                    }
+
+                    if show_reference_chart {
+                        reference_chart(ui, &style, show_arabic_captions, chart_both_orientations);
+                    }
 
                     ui.add_space(30.0);
                     ui.separator();
@@ -635,3 +655,98 @@ fn powered_by_egui_and_eframe(ui: &mut egui::Ui) {
         ui.label(".");
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPACING: f32 = 8.0;
+
+    fn fitted(available: Vec2, glyphs: usize, marker: (f32, Vec2)) -> ClockLayout {
+        ClockLayout::new(
+            available,
+            SPACING,
+            glyphs,
+            marker,
+            &GlyphStyle::default(),
+            4.0,
+            true,
+        )
+    }
+
+    #[test]
+    fn stacks_in_narrow_windows() {
+        let marker = (0.0, Vec2::ZERO);
+        assert!(fitted(vec2(COMPACT_WIDTH - 1.0, 800.0), 2, marker).stacked);
+        assert!(!fitted(vec2(COMPACT_WIDTH, 800.0), 2, marker).stacked);
+    }
+
+    #[test]
+    fn fitted_glyphs_stay_inside() {
+        let style = GlyphStyle::default();
+        let markers = [
+            (0.0, Vec2::ZERO),
+            (MERIDIEM_CAPTION_WIDTH, Vec2::ZERO),
+            (0.0, vec2(MERIDIEM_DOT_WIDTH, 0.0)),
+            (0.0, vec2(0.0, MERIDIEM_DOT_WIDTH)),
+        ];
+        for available in [
+            vec2(300.0, 900.0),
+            vec2(1200.0, 400.0),
+            vec2(2000.0, 2000.0),
+        ] {
+            for glyphs in 1..=3 {
+                for (marker, units) in markers {
+                    let layout = fitted(available, glyphs, (marker, units));
+                    let scale = layout.scale;
+                    let cell = scale * style.cell_size(scale);
+                    let count = glyphs as f32;
+                    let gaps = SPACING * (count - 1.0);
+                    let across = marker
+                        + scale * units.x
+                        + if marker > 0.0 || units.x > 0.0 {
+                            SPACING
+                        } else {
+                            0.0
+                        };
+                    let down = scale * units.y + if units.y > 0.0 { SPACING } else { 0.0 };
+                    let (width, height) = if layout.stacked {
+                        (cell + across, count * cell + gaps + down)
+                    } else {
+                        (count * cell + gaps + across, cell + down)
+                    };
+                    assert!(
+                        width <= available.x + 1e-3 && height <= available.y + 1e-3,
+                        "{width} × {height} in {available:?}, {layout:?}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn keeps_minimum_scale_in_tiny_windows() {
+        let layout = fitted(vec2(20.0, 10.0), 2, (MERIDIEM_CAPTION_WIDTH, Vec2::ZERO));
+        assert_eq!(layout.scale, *Settings::GLYPH_SCALE_RANGE.start());
+    }
+
+    #[test]
+    fn uses_given_scale_unless_fitting() {
+        let layout = ClockLayout::new(
+            vec2(2000.0, 2000.0),
+            SPACING,
+            2,
+            (0.0, Vec2::ZERO),
+            &GlyphStyle::default(),
+            2.5,
+            false,
+        );
+        assert_eq!(
+            layout,
+            ClockLayout {
+                scale: 2.5,
+                stacked: false,
+            }
+        );
+    }
+}
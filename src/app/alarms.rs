@@ -0,0 +1,344 @@
+use super::{paint_number, world_clock, CistercianClockApp, GlyphStyle};
+use crate::{decode, CistercianNumeral};
+use chrono::{DateTime, Datelike, FixedOffset, Timelike, Weekday};
+use egui::{Id, Modal, RichText, TextEdit, Ui, UserAttentionType, ViewportCommand};
+
+/// Scale of the glyph shown for each alarm.
+const ALARM_SCALE: f32 = 1.5;
+
+/// Days an alarm goes off on.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum Repeat {
+    /// Goes off the next time the clock reaches the alarm time, then disables itself.
+    #[default]
+    Once,
+    Daily,
+
+    /// Monday to Friday.
+    Weekdays,
+}
+
+impl Repeat {
+    pub const ALL: [Self; 3] = [Self::Once, Self::Daily, Self::Weekdays];
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Once => "Once",
+            Self::Daily => "Daily",
+            Self::Weekdays => "Weekdays",
+        }
+    }
+
+    fn includes(self, weekday: Weekday) -> bool {
+        match self {
+            Self::Once | Self::Daily => true,
+            Self::Weekdays => !matches!(weekday, Weekday::Sat | Weekday::Sun),
+        }
+    }
+}
+
+/// An alarm at a time of day, in the main clock's time zone.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Alarm {
+    pub label: String,
+
+    /// Time of day as `HHMM`, the value of the clock's hours-minutes glyph in 24-hour mode.
+    pub time: u16,
+    pub enabled: bool,
+    pub repeat: Repeat,
+
+    /// Day the alarm last went off, counted from the start of the common era, so it goes off
+    /// only once on each day, even across restarts.
+    pub last_fired_day: Option<i32>,
+}
+
+impl Default for Alarm {
+    fn default() -> Self {
+        Self {
+            label: "Alarm".to_owned(),
+            time: 700,
+            enabled: true,
+            repeat: Repeat::default(),
+            last_fired_day: None,
+        }
+    }
+}
+
+impl Alarm {
+    /// Whether the alarm should go off at `now`.
+    fn is_due(&self, now: &DateTime<FixedOffset>) -> bool {
+        self.enabled
+            && u32::from(self.time) == now.hour() * 100 + now.minute()
+            && self.repeat.includes(now.weekday())
+            && self.last_fired_day != Some(now.num_days_from_ce())
+    }
+}
+
+/// Parses a 24-hour time written `HH:MM` or `HHMM` into its `HHMM` value.
+fn parse_time(text: &str) -> Option<u16> {
+    let text = text.trim();
+    let (hours, minutes) = text
+        .split_once(':')
+        .or_else(|| (text.len() > 2).then(|| text.split_at(text.len() - 2)))?;
+    if minutes.len() != 2 {
+        return None;
+    }
+    let hours: u16 = hours.parse().ok()?;
+    let minutes: u16 = minutes.parse().ok()?;
+    (hours < 24 && minutes < 60).then_some(hours * 100 + minutes)
+}
+
+/// `HHMM` value as `HH:MM`.
+fn format_time(time: u16) -> String {
+    format!("{:02}:{:02}", time / 100, time % 100)
+}
+
+/// State of the form for adding an alarm. Not persisted.
+#[derive(Debug)]
+pub(super) struct AlarmDraft {
+    label: String,
+    time: String,
+    repeat: Repeat,
+    error: Option<String>,
+}
+
+impl Default for AlarmDraft {
+    fn default() -> Self {
+        Self {
+            label: String::new(),
+            time: "07:00".to_owned(),
+            repeat: Repeat::default(),
+            error: None,
+        }
+    }
+}
+
+impl CistercianClockApp {
+    /// Sets off any alarms due now. Called every frame, so alarms go off even while the alarm
+    /// panel is hidden.
+    pub(super) fn alarms_tick(&mut self, ctx: &egui::Context) {
+        // The main clock shows the unknown zone, and alarms wait until it is fixed.
+        let Some(now) = world_clock::now_in_zone(self.settings.time_zone.as_deref()) else {
+            return;
+        };
+        let mut fired = false;
+        for (index, alarm) in self.alarms.iter_mut().enumerate() {
+            if alarm.is_due(&now) {
+                alarm.last_fired_day = Some(now.num_days_from_ce());
+                if alarm.repeat == Repeat::Once {
+                    alarm.enabled = false;
+                }
+                if !self.ringing_alarms.contains(&index) {
+                    self.ringing_alarms.push(index);
+                }
+                fired = true;
+            }
+        }
+        if fired {
+            ctx.send_viewport_cmd(ViewportCommand::RequestUserAttention(
+                UserAttentionType::Critical,
+            ));
+        }
+    }
+
+    /// Overlay listing the alarms which have gone off, until the user dismisses it.
+    pub(super) fn ringing_alarms_ui(&mut self, ctx: &egui::Context, style: &GlyphStyle) {
+        if self.ringing_alarms.is_empty() {
+            return;
+        }
+
+        let alarms = &self.alarms;
+        let scale = self.settings.glyph_scale;
+        let mut dismissed = false;
+        let modal = Modal::new(Id::new("ringing_alarms")).show(ctx, |ui| {
+            ui.heading("Alarm");
+            ui.add_space(10.0);
+            for alarm in self
+                .ringing_alarms
+                .iter()
+                .filter_map(|&index| alarms.get(index))
+            {
+                ui.horizontal(|ui| {
+                    paint_alarm_time(ui, style, alarm.time, scale);
+                    ui.label(RichText::new(&alarm.label).heading());
+                });
+            }
+            ui.add_space(10.0);
+            dismissed = ui.button("Dismiss").clicked();
+        });
+        if dismissed || modal.should_close() {
+            self.ringing_alarms.clear();
+        }
+    }
+
+    /// Alarm list, with a form to add alarms by typing a time or composing it in the glyph
+    /// builder.
+    pub(super) fn alarms_ui(&mut self, ui: &mut Ui, style: &GlyphStyle) {
+        ui.heading("Alarms");
+        ui.add_space(15.0);
+
+        let mut removed = None;
+        for (index, alarm) in self.alarms.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut alarm.enabled, "");
+                paint_alarm_time(ui, style, alarm.time, ALARM_SCALE);
+                ui.label(format_time(alarm.time));
+                ui.add(TextEdit::singleline(&mut alarm.label).desired_width(120.0));
+                egui::ComboBox::from_id_salt(("alarm_repeat", index))
+                    .selected_text(alarm.repeat.label())
+                    .show_ui(ui, |ui| {
+                        for repeat in Repeat::ALL {
+                            ui.selectable_value(&mut alarm.repeat, repeat, repeat.label());
+                        }
+                    });
+                if ui.button("Remove").clicked() {
+                    removed = Some(index);
+                }
+            });
+        }
+        if let Some(index) = removed {
+            self.alarms.remove(index);
+            self.ringing_alarms.clear();
+        }
+
+        ui.add_space(15.0);
+        let draft = &mut self.alarm_draft;
+        ui.horizontal(|ui| {
+            ui.label("Time:");
+            ui.add(
+                TextEdit::singleline(&mut draft.time)
+                    .hint_text("HH:MM")
+                    .desired_width(60.0),
+            );
+            if ui
+                .button("From glyph")
+                .on_hover_text("Use the glyph builder value as HHMM")
+                .clicked()
+            {
+                match decode::decode(self.builder_strokes) {
+                    Ok(numeral) => {
+                        draft.time = format_time(numeral.value());
+                        draft.error = None;
+                    }
+                    Err(error) => draft.error = Some(format!("Glyph builder: {error}")),
+                }
+            }
+            ui.label("Label:");
+            ui.add(
+                TextEdit::singleline(&mut draft.label)
+                    .hint_text("Alarm")
+                    .desired_width(120.0),
+            );
+            egui::ComboBox::from_id_salt("alarm_draft_repeat")
+                .selected_text(draft.repeat.label())
+                .show_ui(ui, |ui| {
+                    for repeat in Repeat::ALL {
+                        ui.selectable_value(&mut draft.repeat, repeat, repeat.label());
+                    }
+                });
+            if ui.button("Add").clicked() {
+                match parse_time(&draft.time) {
+                    Some(time) => {
+                        let label = match draft.label.trim() {
+                            "" => Alarm::default().label,
+                            label => label.to_owned(),
+                        };
+                        self.alarms.push(Alarm {
+                            label,
+                            time,
+                            repeat: draft.repeat,
+                            ..Default::default()
+                        });
+                        *draft = AlarmDraft::default();
+                    }
+                    None => {
+                        draft.error = Some(format!(
+                            "`{}` is not a 24-hour time such as 07:30",
+                            draft.time
+                        ));
+                    }
+                }
+            }
+        });
+        if let Some(error) = &draft.error {
+            ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+        }
+    }
+}
+
+fn paint_alarm_time(ui: &mut Ui, style: &GlyphStyle, time: u16, scale: f32) {
+    if let Ok(numeral) = CistercianNumeral::try_from(time) {
+        paint_number(ui, style, numeral, Some(scale), None);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    /// `hour`:`minute` on 2025-01-`day`, where the 4th is a Saturday and the 6th a Monday.
+    fn at(day: u32, hour: u32, minute: u32) -> DateTime<FixedOffset> {
+        FixedOffset::east_opt(0)
+            .unwrap()
+            .with_ymd_and_hms(2025, 1, day, hour, minute, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn parses_times() {
+        assert_eq!(parse_time("0730"), Some(730));
+        assert_eq!(parse_time("07:30"), Some(730));
+        assert_eq!(parse_time(" 7:30 "), Some(730));
+        assert_eq!(parse_time("2359"), Some(2359));
+        assert_eq!(parse_time("123"), Some(123));
+        assert_eq!(parse_time("00:00"), Some(0));
+    }
+
+    #[test]
+    fn rejects_invalid_times() {
+        for text in [
+            "24:00", "2400", "12:60", "7:5", "75", "", "ab:cd", "07:30:00",
+        ] {
+            assert_eq!(parse_time(text), None, "{text:?}");
+        }
+    }
+
+    #[test]
+    fn goes_off_at_its_time() {
+        let alarm = Alarm::default();
+        assert!(alarm.is_due(&at(6, 7, 0)));
+        assert!(!alarm.is_due(&at(6, 7, 1)));
+        assert!(!alarm.is_due(&at(6, 19, 0)));
+        assert!(!Alarm {
+            enabled: false,
+            ..Alarm::default()
+        }
+        .is_due(&at(6, 7, 0)));
+    }
+
+    #[test]
+    fn weekdays_skip_weekend() {
+        let alarm = Alarm {
+            repeat: Repeat::Weekdays,
+            ..Alarm::default()
+        };
+        assert!(alarm.is_due(&at(6, 7, 0)));
+        assert!(!alarm.is_due(&at(4, 7, 0)));
+        assert!(!alarm.is_due(&at(5, 7, 0)));
+        assert!(Alarm::default().is_due(&at(4, 7, 0)));
+    }
+
+    #[test]
+    fn goes_off_once_a_day() {
+        let now = at(6, 7, 0);
+        let alarm = Alarm {
+            repeat: Repeat::Daily,
+            last_fired_day: Some(now.num_days_from_ce()),
+            ..Alarm::default()
+        };
+        assert!(!alarm.is_due(&now));
+        assert!(alarm.is_due(&at(7, 7, 0)));
+    }
+}
@@ -0,0 +1,120 @@
+use super::{CistercianClockApp, GlyphStyle};
+use crate::{
+    decode::{self, DecodeError, QuadrantStrokes},
+    glyph::{GlyphPoint, Quadrant, Segment},
+    widget::paint_outline,
+};
+use egui::{Pos2, RichText, Sense, Ui, Vec2};
+
+/// Scale of the clickable glyph, large enough to hit individual strokes.
+const BUILDER_SCALE: f32 = 8.0;
+
+/// Furthest a click can land from a stroke position, in glyph units, and still toggle it.
+const HIT_DISTANCE: f32 = 3.0;
+
+impl CistercianClockApp {
+    /// Panel where the user composes a glyph stroke by stroke and reads back its value. The glyph
+    /// is drawn in the chosen orientation, while strokes are identified in the vertical form.
+    pub(super) fn glyph_builder_ui(&mut self, ui: &mut Ui, style: &GlyphStyle) {
+        ui.heading("Glyph Builder");
+        ui.label("Click a stroke position to add or remove it.");
+        ui.add_space(15.0);
+
+        let size = Vec2::splat(BUILDER_SCALE * style.cell_size(BUILDER_SCALE));
+        let (response, painter) = ui.allocate_painter(size, Sense::click());
+        let centre = response.rect.center();
+
+        let pointer = response.hover_pos().map(|position| {
+            let offset = (position - centre) / BUILDER_SCALE;
+            style
+                .orientation
+                .unorient(GlyphPoint::new(offset.x, offset.y))
+        });
+        let hovered = pointer.and_then(nearest_stroke);
+        if let (true, Some((quadrant, segment))) = (response.clicked(), hovered) {
+            self.builder_strokes[quadrant as usize].toggle(segment.kind);
+        }
+
+        let result = decode::decode(self.builder_strokes);
+        let invalid_quadrant = match &result {
+            Err(DecodeError::InvalidCombination { quadrant, .. }) => Some(*quadrant),
+            _ => None,
+        };
+
+        // Positions not picked are drawn alone, so they do not join the glyph. The ghost colour is
+        // mixed with the background rather than translucent, so overlapping shapes do not show.
+        let ghost = ui
+            .visuals()
+            .panel_fill
+            .lerp_to_gamma(ui.visuals().weak_text_color(), 0.4);
+        let warn = ui.visuals().warn_fg_color;
+        let stem = Segment::stem();
+        let mut picked = vec![(stem, style.colours.stroke_colour(stem.kind))];
+        for quadrant in Quadrant::ALL {
+            let strokes = self.builder_strokes[quadrant as usize];
+            for kind in QuadrantStrokes::KINDS {
+                let segment = Segment::stroke(quadrant, kind);
+                if strokes.contains(kind) {
+                    let colour = if invalid_quadrant == Some(quadrant) {
+                        warn
+                    } else {
+                        style.colours.stroke_colour(kind)
+                    };
+                    picked.push((segment, colour));
+                } else {
+                    let colour = if hovered == Some((quadrant, segment)) {
+                        ui.visuals().strong_text_color()
+                    } else {
+                        ghost
+                    };
+                    for outline in
+                        style.outlines(&[segment.oriented(style.orientation)], BUILDER_SCALE)
+                    {
+                        paint_outline(&painter, centre, BUILDER_SCALE, &outline, colour);
+                    }
+                }
+            }
+        }
+        let segments: Vec<Segment> = picked
+            .iter()
+            .map(|(segment, _)| segment.oriented(style.orientation))
+            .collect();
+        for (outline, (_, colour)) in style.outlines(&segments, BUILDER_SCALE).iter().zip(&picked) {
+            paint_outline(&painter, centre, BUILDER_SCALE, outline, *colour);
+        }
+
+        ui.add_space(15.0);
+        match result {
+            Ok(numeral) => ui.label(RichText::new(format!("Value: {numeral:#}")).heading()),
+            Err(error) => ui.label(RichText::new(error.to_string()).color(warn)),
+        };
+        if ui.button("Clear").clicked() {
+            self.builder_strokes = Default::default();
+        }
+    }
+}
+
+/// Stroke position closest to `pointer`, given in glyph units for the vertical form, if it is
+/// close enough to click.
+fn nearest_stroke(pointer: GlyphPoint) -> Option<(Quadrant, Segment)> {
+    let pointer = Pos2::new(pointer.x, pointer.y);
+    Quadrant::ALL
+        .into_iter()
+        .flat_map(|quadrant| {
+            QuadrantStrokes::KINDS
+                .into_iter()
+                .map(move |kind| (quadrant, Segment::stroke(quadrant, kind)))
+        })
+        .map(|(quadrant, segment)| (distance_to_segment(pointer, &segment), quadrant, segment))
+        .filter(|(distance, ..)| *distance <= HIT_DISTANCE)
+        .min_by(|(first, ..), (second, ..)| first.total_cmp(second))
+        .map(|(_, quadrant, segment)| (quadrant, segment))
+}
+
+fn distance_to_segment(point: Pos2, segment: &Segment) -> f32 {
+    let start = Pos2::new(segment.start.x, segment.start.y);
+    let end = Pos2::new(segment.end.x, segment.end.y);
+    let along = end - start;
+    let t = ((point - start).dot(along) / along.length_sq()).clamp(0.0, 1.0);
+    point.distance(start + t * along)
+}
@@ -0,0 +1,54 @@
+use super::{
+    paint_number,
+    settings::{MonthDayStyle, Settings},
+    GlyphStyle,
+};
+use crate::CistercianNumeral;
+use chrono::{DateTime, Datelike, FixedOffset};
+use egui::{RichText, Ui};
+
+/// Row of date glyphs for `now`: the year, then month and day, and optionally the day of the
+/// year and ISO week number.
+pub(super) fn date_row(
+    ui: &mut Ui,
+    style: &GlyphStyle,
+    now: &DateTime<FixedOffset>,
+    settings: &Settings,
+) {
+    let scale = (settings.glyph_scale / 2.0).max(1.0);
+    let captions = settings.show_arabic_captions;
+    let date = &settings.date;
+
+    let mut glyphs: Vec<(&str, u32)> = Vec::new();
+    if let Ok(year) = u32::try_from(now.year()) {
+        glyphs.push(("Year", year));
+    }
+    match date.month_day {
+        MonthDayStyle::Combined => glyphs.push(("Month–day", now.month() * 100 + now.day())),
+        MonthDayStyle::Separate => {
+            glyphs.push(("Month", now.month()));
+            glyphs.push(("Day", now.day()));
+        }
+    }
+    if date.show_day_of_year {
+        glyphs.push(("Day of year", now.ordinal()));
+    }
+    if date.show_iso_week {
+        glyphs.push(("ISO week", now.iso_week().week()));
+    }
+
+    ui.horizontal_top(|ui| {
+        for (caption, value) in glyphs {
+            let Ok(numeral) = CistercianNumeral::try_from(value) else {
+                continue;
+            };
+            ui.vertical(|ui| {
+                ui.label(RichText::new(caption).small());
+                ui.horizontal_top(|ui| {
+                    paint_number(ui, style, numeral, Some(scale), Some(captions));
+                });
+            });
+            ui.add_space(16.0);
+        }
+    });
+}
@@ -0,0 +1,65 @@
+use super::{paint_compound_number, CistercianClockApp, GlyphStyle};
+use crate::{glyph::Grouping, CompoundNumeral};
+use chrono::Utc;
+use egui::{RichText, Slider, TextEdit, Ui};
+
+impl CistercianClockApp {
+    /// Text box where the user enters an Arabic number and sees it drawn in glyphs.
+    pub(super) fn converter_ui(&mut self, ui: &mut Ui, style: &GlyphStyle) {
+        ui.heading("Convert a Number");
+        ui.add_space(15.0);
+
+        ui.horizontal(|ui| {
+            ui.label("Number:");
+            ui.add(
+                TextEdit::singleline(&mut self.converter_input)
+                    .hint_text("1984")
+                    .desired_width(200.0),
+            );
+            if ui
+                .button("Unix time")
+                .on_hover_text("Enter the current Unix timestamp")
+                .clicked()
+            {
+                self.converter_input = Utc::now().timestamp().max(0).to_string();
+            }
+            ui.add_space(16.0);
+            ui.add(Slider::new(&mut self.converter_scale, 1.0..=8.0).text("Scale"));
+        });
+        ui.horizontal(|ui| {
+            ui.label("Above 9,999:");
+            ui.radio_value(
+                &mut self.converter_grouping,
+                Grouping::Positional,
+                "Positional",
+            )
+            .on_hover_text("One glyph per four digits, read left to right");
+            ui.radio_value(
+                &mut self.converter_grouping,
+                Grouping::Bracketed,
+                "Bracketed",
+            )
+            .on_hover_text("A bar under the glyph for each power of 10,000");
+        });
+        ui.add_space(15.0);
+
+        match self.converter_input.parse::<CompoundNumeral>() {
+            Ok(number) => {
+                ui.horizontal_top(|ui| {
+                    paint_compound_number(
+                        ui,
+                        style,
+                        number,
+                        self.converter_grouping,
+                        self.converter_scale,
+                        self.settings.show_arabic_captions,
+                    );
+                });
+            }
+            Err(error) => {
+                let colour = ui.visuals().error_fg_color;
+                ui.label(RichText::new(error.to_string()).color(colour));
+            }
+        }
+    }
+}
@@ -0,0 +1,281 @@
+use super::{paint_number, wrapping_numeral, CistercianClockApp, GlyphStyle};
+use crate::palette::Colours;
+use core::time::Duration;
+use egui::{DragValue, RichText, Ui, UserAttentionType, ViewportCommand};
+
+/// How long the completion alert stays in each state of its flash.
+const FLASH_PERIOD: f64 = 0.5;
+
+/// Countdown timed against egui's monotonic input clock. The remaining time is worked out from
+/// the end time on every frame, rather than counted down, so it stays accurate however often
+/// the app repaints.
+#[derive(Debug, Default)]
+pub(super) struct Countdown {
+    /// Input time the countdown reaches zero, while it is running.
+    ends_at: Option<Duration>,
+
+    /// Time left when the countdown was paused, or `None` if it has not been started.
+    paused_with: Option<Duration>,
+
+    /// Set when the countdown reaches zero, until the user dismisses the alert.
+    finished: bool,
+}
+
+impl Countdown {
+    fn is_running(&self) -> bool {
+        self.ends_at.is_some()
+    }
+
+    fn remaining(&self, now: Duration, duration: Duration) -> Duration {
+        match (self.ends_at, self.paused_with) {
+            (Some(ends_at), _) => ends_at.saturating_sub(now),
+            (None, Some(paused_with)) => paused_with,
+            (None, None) if self.finished => Duration::ZERO,
+            (None, None) => duration,
+        }
+    }
+
+    fn start(&mut self, now: Duration, duration: Duration) {
+        let remaining = self.paused_with.take().unwrap_or(duration);
+        self.ends_at = Some(now + remaining);
+        self.finished = false;
+    }
+
+    fn pause(&mut self, now: Duration) {
+        if let Some(ends_at) = self.ends_at.take() {
+            self.paused_with = Some(ends_at.saturating_sub(now));
+        }
+    }
+
+    fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Marks the countdown finished once it reaches zero, returning `true` on the frame that
+    /// happens.
+    fn update(&mut self, now: Duration) -> bool {
+        match self.ends_at {
+            Some(ends_at) if now >= ends_at => {
+                self.ends_at = None;
+                self.finished = true;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Whole seconds in `remaining`, rounded up so the glyphs show 00:01 until the very end.
+fn whole_seconds(remaining: Duration) -> u64 {
+    remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0)
+}
+
+/// `seconds` as `MM:SS`.
+fn format_seconds(seconds: u64) -> String {
+    format!("{:02}:{:02}", seconds / 60, seconds % 60)
+}
+
+impl CistercianClockApp {
+    /// Checks whether the countdown has finished, and schedules the next repaint it needs.
+    /// Called every frame, so the alert fires even while the countdown panel is hidden.
+    pub(super) fn countdown_tick(&mut self, ctx: &egui::Context) {
+        let now = Duration::from_secs_f64(ctx.input(|input| input.time));
+        let countdown = &mut self.countdown;
+        if countdown.update(now) {
+            ctx.send_viewport_cmd(ViewportCommand::RequestUserAttention(
+                UserAttentionType::Critical,
+            ));
+        }
+
+        if let Some(ends_at) = countdown.ends_at {
+            // Wake up as the displayed second changes, or when the countdown ends.
+            let remaining = ends_at.saturating_sub(now);
+            let until_next_second = Duration::from_nanos(u64::from(remaining.subsec_nanos()));
+            ctx.request_repaint_after(until_next_second.max(Duration::from_millis(1)));
+        } else if countdown.finished {
+            ctx.request_repaint_after(Duration::from_secs_f64(FLASH_PERIOD));
+        }
+    }
+
+    /// Countdown timer with saved presets, which flashes when it reaches zero.
+    pub(super) fn countdown_ui(&mut self, ui: &mut Ui, style: &GlyphStyle) {
+        ui.heading("Countdown");
+        ui.add_space(15.0);
+
+        let time = ui.input(|input| input.time);
+        let now = Duration::from_secs_f64(time);
+        let duration = Duration::from_secs(self.settings.countdown.duration_seconds);
+        let countdown = &mut self.countdown;
+        let remaining = countdown.remaining(now, duration);
+        let remaining_seconds = whole_seconds(remaining);
+        let alert_style;
+        let glyph_style = if countdown.finished && (time / FLASH_PERIOD) as u64 % 2 == 0 {
+            let error = ui.visuals().error_fg_color;
+            alert_style = GlyphStyle {
+                colours: Colours {
+                    colour_0: error,
+                    colour_1: error,
+                    colour_2: error,
+                    colour_3: error,
+                    colour_4: error,
+                    colour_6: error,
+                },
+                ..*style
+            };
+            &alert_style
+        } else {
+            style
+        };
+        ui.horizontal(|ui| {
+            let scale = Some(self.settings.glyph_scale);
+            paint_number(
+                ui,
+                glyph_style,
+                wrapping_numeral(remaining_seconds / 60),
+                scale,
+                None,
+            );
+            paint_number(
+                ui,
+                glyph_style,
+                wrapping_numeral(remaining_seconds % 60),
+                scale,
+                None,
+            );
+        });
+        let caption = RichText::new(format_seconds(remaining_seconds)).monospace();
+        if countdown.finished {
+            ui.label(caption.color(ui.visuals().error_fg_color).strong());
+        } else {
+            ui.label(caption);
+        }
+        ui.add_space(10.0);
+
+        ui.horizontal(|ui| {
+            if countdown.finished {
+                if ui.button("Dismiss").clicked() {
+                    countdown.reset();
+                }
+            } else if countdown.is_running() {
+                if ui.button("Pause").clicked() {
+                    countdown.pause(now);
+                }
+                if ui.button("Reset").clicked() {
+                    countdown.reset();
+                }
+            } else {
+                if ui
+                    .add_enabled(remaining > Duration::ZERO, egui::Button::new("Start"))
+                    .clicked()
+                {
+                    countdown.start(now, duration);
+                }
+                if ui.button("Reset").clicked() {
+                    countdown.reset();
+                }
+            }
+        });
+        ui.add_space(10.0);
+
+        let options = &mut self.settings.countdown;
+        let editable = !countdown.is_running() && countdown.paused_with.is_none();
+        ui.add_enabled_ui(editable, |ui| {
+            ui.horizontal(|ui| {
+                let mut minutes = options.duration_seconds / 60;
+                let mut seconds = options.duration_seconds % 60;
+                ui.label("Minutes:");
+                ui.add(DragValue::new(&mut minutes).range(0..=9_999));
+                ui.label("Seconds:");
+                ui.add(DragValue::new(&mut seconds).range(0..=59));
+                options.duration_seconds = minutes * 60 + seconds;
+
+                let is_saved = options.presets.contains(&options.duration_seconds);
+                if ui
+                    .add_enabled(!is_saved, egui::Button::new("Save preset"))
+                    .clicked()
+                {
+                    options.presets.push(options.duration_seconds);
+                    options.presets.sort_unstable();
+                }
+            });
+            ui.horizontal_wrapped(|ui| {
+                let mut removed = None;
+                for &preset in &options.presets {
+                    let response = ui
+                        .button(format_seconds(preset))
+                        .on_hover_text("Right-click to remove");
+                    if response.clicked() {
+                        options.duration_seconds = preset;
+                    }
+                    if response.secondary_clicked() {
+                        removed = Some(preset);
+                    }
+                }
+                if let Some(removed) = removed {
+                    options.presets.retain(|&preset| preset != removed);
+                }
+            });
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MINUTE: Duration = Duration::from_secs(60);
+
+    fn seconds(seconds: f64) -> Duration {
+        Duration::from_secs_f64(seconds)
+    }
+
+    #[test]
+    fn counts_down_from_duration() {
+        let mut countdown = Countdown::default();
+        assert_eq!(countdown.remaining(seconds(5.0), MINUTE), MINUTE);
+        countdown.start(seconds(10.0), MINUTE);
+        assert_eq!(countdown.remaining(seconds(25.5), MINUTE), seconds(44.5));
+    }
+
+    #[test]
+    fn pause_keeps_remaining_time() {
+        let mut countdown = Countdown::default();
+        countdown.start(seconds(10.0), MINUTE);
+        countdown.pause(seconds(25.0));
+        assert!(!countdown.is_running());
+        assert_eq!(countdown.remaining(seconds(90.0), MINUTE), seconds(45.0));
+
+        // Resuming counts down from where it paused, not from the full duration.
+        countdown.start(seconds(100.0), MINUTE);
+        assert_eq!(countdown.remaining(seconds(110.0), MINUTE), seconds(35.0));
+        assert!(!countdown.update(seconds(144.9)));
+        assert!(countdown.update(seconds(145.0)));
+    }
+
+    #[test]
+    fn finishes_once() {
+        let mut countdown = Countdown::default();
+        countdown.start(Duration::ZERO, MINUTE);
+        assert!(!countdown.update(seconds(59.999)));
+        assert!(!countdown.finished);
+
+        // A late frame still fires.
+        assert!(countdown.update(seconds(60.25)));
+        assert!(countdown.finished);
+        assert!(!countdown.is_running());
+        assert!(!countdown.update(seconds(61.0)));
+        assert_eq!(countdown.remaining(seconds(61.0), MINUTE), Duration::ZERO);
+
+        countdown.reset();
+        assert_eq!(countdown.remaining(seconds(62.0), MINUTE), MINUTE);
+    }
+
+    #[test]
+    fn rounds_up_partial_seconds() {
+        assert_eq!(whole_seconds(Duration::ZERO), 0);
+        assert_eq!(whole_seconds(Duration::from_millis(1)), 1);
+        assert_eq!(whole_seconds(seconds(59.001)), 60);
+        assert_eq!(whole_seconds(MINUTE), 60);
+        assert_eq!(format_seconds(whole_seconds(seconds(0.4))), "00:01");
+    }
+}
@@ -0,0 +1,212 @@
+use super::{paint_number, settings::PaletteChoice, CistercianClockApp, GlyphStyle};
+use crate::{
+    palette::{
+        Colours, Palette, PaletteFormat, PalettePreset, DARK_CISTERCIAN_NUMERAL_COLOURS,
+        LIGHT_CISTERCIAN_NUMERAL_COLOURS, MIN_GRAPHIC_CONTRAST,
+    },
+    theme::ClockTheme,
+    CistercianNumeral,
+};
+use egui::{vec2, Grid, RichText, Sense, TextEdit, Ui};
+
+/// Scale of the preview glyphs.
+const PREVIEW_SCALE: f32 = 2.0;
+
+/// Numerals which between them use every kind of stroke.
+const PREVIEW_NUMERALS: [u16; 3] = [9_999, 3_333, 4_444];
+
+/// Palette being edited, and the import and export text. Not persisted.
+#[derive(Debug)]
+pub(super) struct PaletteEditor {
+    draft: Palette,
+    format: PaletteFormat,
+    text: String,
+    error: Option<String>,
+}
+
+impl Default for PaletteEditor {
+    fn default() -> Self {
+        Self {
+            draft: Palette {
+                name: "My palette".to_owned(),
+                colours: DARK_CISTERCIAN_NUMERAL_COLOURS,
+            },
+            format: PaletteFormat::default(),
+            text: String::new(),
+            error: None,
+        }
+    }
+}
+
+fn paint_swatches(ui: &mut Ui, colours: &Colours) {
+    for (kind, _) in Colours::KINDS {
+        let (rect, _) = ui.allocate_exact_size(vec2(12.0, 12.0), Sense::hover());
+        ui.painter()
+            .rect_filled(rect, 2.0, colours.stroke_colour(kind));
+    }
+}
+
+/// Lowest stroke contrast of `colours` against `background`, flagged when it is too low.
+fn contrast_label(ui: &mut Ui, colours: &Colours, background: egui::Color32) {
+    let contrast = colours.min_contrast(background);
+    let text = RichText::new(format!("{contrast:.1}:1"));
+    if contrast < MIN_GRAPHIC_CONTRAST {
+        ui.label(text.color(ui.visuals().error_fg_color))
+            .on_hover_text(format!("Below {MIN_GRAPHIC_CONTRAST}:1"));
+    } else {
+        ui.label(text);
+    }
+}
+
+/// Lowest stroke contrast of the draft against the panel fill of both themes, and of each preset
+/// against the fill of the theme its variant is for.
+fn contrast_checks(ui: &mut Ui, draft: &Palette, theme: &ClockTheme) {
+    let dark_fill = theme.dark.panel_fill;
+    let light_fill = theme.light.panel_fill;
+    ui.label(RichText::new("Lowest stroke contrast").strong());
+    Grid::new("palette_contrast")
+        .num_columns(3)
+        .spacing([24.0, 4.0])
+        .show(ui, |ui| {
+            ui.label("");
+            ui.label("Dark theme");
+            ui.label("Light theme");
+            ui.end_row();
+
+            ui.label(&draft.name);
+            contrast_label(ui, &draft.colours, dark_fill);
+            contrast_label(ui, &draft.colours, light_fill);
+            ui.end_row();
+
+            for preset in PalettePreset::ALL {
+                ui.label(preset.name());
+                contrast_label(ui, &preset.colours(true), dark_fill);
+                contrast_label(ui, &preset.colours(false), light_fill);
+                ui.end_row();
+            }
+        });
+}
+
+impl CistercianClockApp {
+    /// Saved palettes, with an editor to make new ones and a box to import or export them as
+    /// JSON or TOML.
+    pub(super) fn palette_editor_ui(&mut self, ui: &mut Ui, style: &GlyphStyle) {
+        ui.heading("Palettes");
+        ui.add_space(15.0);
+
+        let settings = &mut self.settings;
+        let editor = &mut self.palette_editor;
+        let mut removed = None;
+        for (index, palette) in settings.palettes.iter().enumerate() {
+            ui.horizontal(|ui| {
+                paint_swatches(ui, &palette.colours);
+                ui.label(&palette.name);
+                let choice = PaletteChoice::Saved(palette.name.clone());
+                if ui
+                    .add_enabled(settings.palette != choice, egui::Button::new("Use"))
+                    .clicked()
+                {
+                    settings.palette = choice;
+                }
+                if ui.button("Edit").clicked() {
+                    editor.draft = palette.clone();
+                }
+                if ui.button("Delete").clicked() {
+                    removed = Some(index);
+                }
+            });
+        }
+        if let Some(index) = removed {
+            settings.remove_palette(index);
+        }
+        ui.add_space(15.0);
+
+        let draft = &mut editor.draft;
+        ui.horizontal(|ui| {
+            ui.label("Name:");
+            ui.add(TextEdit::singleline(&mut draft.name).desired_width(160.0));
+        });
+        ui.horizontal_top(|ui| {
+            Grid::new("palette_editor_colours")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    for (kind, label) in Colours::KINDS {
+                        ui.label(label);
+                        ui.color_edit_button_srgba(draft.colours.stroke_colour_mut(kind));
+                        ui.end_row();
+                    }
+                });
+            ui.add_space(15.0);
+            let preview_style = GlyphStyle {
+                colours: draft.colours,
+                ..*style
+            };
+            for value in PREVIEW_NUMERALS {
+                if let Ok(numeral) = CistercianNumeral::try_from(value) {
+                    paint_number(ui, &preview_style, numeral, Some(PREVIEW_SCALE), None);
+                }
+            }
+        });
+        ui.horizontal(|ui| {
+            if ui.button("Start from dark").clicked() {
+                draft.colours = DARK_CISTERCIAN_NUMERAL_COLOURS;
+            }
+            if ui.button("Start from light").clicked() {
+                draft.colours = LIGHT_CISTERCIAN_NUMERAL_COLOURS;
+            }
+            let name = draft.name.trim();
+            let exists = settings.palettes.iter().any(|palette| palette.name == name);
+            let save_label = if exists { "Replace" } else { "Save" };
+            if ui
+                .add_enabled(!name.is_empty(), egui::Button::new(save_label))
+                .clicked()
+            {
+                let palette = Palette {
+                    name: name.to_owned(),
+                    colours: draft.colours,
+                };
+                match settings
+                    .palettes
+                    .iter_mut()
+                    .find(|saved| saved.name == palette.name)
+                {
+                    Some(saved) => *saved = palette.clone(),
+                    None => settings.palettes.push(palette.clone()),
+                }
+                settings.palette = PaletteChoice::Saved(palette.name);
+            }
+        });
+        ui.add_space(15.0);
+
+        ui.horizontal(|ui| {
+            ui.label("Format:");
+            ui.radio_value(&mut editor.format, PaletteFormat::Json, "JSON");
+            ui.radio_value(&mut editor.format, PaletteFormat::Toml, "TOML");
+            if ui.button("Export").clicked() {
+                editor.text = editor.draft.export(editor.format);
+                editor.error = None;
+            }
+            if ui.button("Import").clicked() {
+                match Palette::import(&editor.text, editor.format) {
+                    Ok(palette) => {
+                        editor.draft = palette;
+                        editor.error = None;
+                    }
+                    Err(error) => editor.error = Some(error.to_string()),
+                }
+            }
+        });
+        ui.add(
+            TextEdit::multiline(&mut editor.text)
+                .code_editor()
+                .desired_rows(8)
+                .hint_text("Paste a palette here to import it"),
+        );
+        if let Some(error) = &editor.error {
+            ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+        }
+        ui.add_space(15.0);
+
+        contrast_checks(ui, &editor.draft, &self.theme);
+    }
+}
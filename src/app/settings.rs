@@ -0,0 +1,647 @@
+use super::world_clock::zone_picker;
+use crate::{
+    glyph::Orientation,
+    palette::{
+        Colours, Palette, PalettePreset, DARK_CISTERCIAN_NUMERAL_COLOURS,
+        LIGHT_CISTERCIAN_NUMERAL_COLOURS,
+    },
+    style::{GlyphStyle, LineCap, LineJoin, Pen, StrokeStyle},
+    transition::TransitionStyle,
+};
+use egui::{ComboBox, Slider, Ui};
+
+/// Whether the clock glyph counts hours 0–23, or 1–12.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum HourFormat {
+    #[default]
+    TwentyFourHour,
+    TwelveHour,
+}
+
+/// How 12-hour mode marks whether a time is before or after noon.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum MeridiemMarker {
+    /// An `AM` or `PM` caption after the clock glyphs.
+    #[default]
+    Caption,
+
+    /// A dot beside the hours-minutes glyph, level with the top of the stem before noon and
+    /// with the bottom after noon. With a horizontal stem, the dot sits under the glyph, below
+    /// the left end of the stem before noon and the right end after noon.
+    Dot,
+}
+
+/// Optional glyph after the seconds, for time finer than a second.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum SubSecond {
+    #[default]
+    Off,
+
+    /// Hundredths of a second, 0–99.
+    Centiseconds,
+
+    /// Thousandths of a second, 0–999.
+    Milliseconds,
+}
+
+impl SubSecond {
+    /// Fraction of the second in `nanos` as shown by the glyph, or `None` when it is off.
+    pub fn value(self, nanos: u32) -> Option<u32> {
+        match self {
+            Self::Off => None,
+            Self::Centiseconds => Some(nanos / 10_000_000),
+            Self::Milliseconds => Some(nanos / 1_000_000),
+        }
+    }
+}
+
+/// Whether the month and day share one `MMDD` glyph, or have a glyph each.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum MonthDayStyle {
+    #[default]
+    Combined,
+    Separate,
+}
+
+/// What the date row shows, after the year.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct DateOptions {
+    pub month_day: MonthDayStyle,
+
+    /// Day of the year, 1–366.
+    pub show_day_of_year: bool,
+    pub show_iso_week: bool,
+}
+
+/// Countdown length, and the lengths saved as presets, in seconds.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct CountdownOptions {
+    pub duration_seconds: u64,
+    pub presets: Vec<u64>,
+}
+
+impl Default for CountdownOptions {
+    fn default() -> Self {
+        Self {
+            duration_seconds: 5 * 60,
+            presets: vec![60, 5 * 60, 10 * 60, 25 * 60],
+        }
+    }
+}
+
+/// Whether, and how, the clock glyphs animate between values.
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct TransitionOptions {
+    pub enabled: bool,
+    pub style: TransitionStyle,
+
+    /// Length of each transition. Kept under a second, so it finishes before the next tick.
+    pub duration_seconds: f32,
+}
+
+impl TransitionOptions {
+    pub const DURATION_RANGE: core::ops::RangeInclusive<f32> = 0.1..=0.9;
+}
+
+impl Default for TransitionOptions {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            style: TransitionStyle::default(),
+            duration_seconds: 0.4,
+        }
+    }
+}
+
+/// Which numeral palette to draw glyphs with. Apart from following the theme, the choice does
+/// not depend on whether egui is in dark or light mode.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum PaletteChoice {
+    /// Dark palette with the dark egui theme, and light palette with the light one.
+    #[default]
+    FollowTheme,
+    Dark,
+    Light,
+
+    /// A built-in colour-blind-safe or high-contrast palette, in its variant for the theme.
+    Preset(PalettePreset),
+
+    /// One of the user's saved palettes, by name.
+    Saved(String),
+}
+
+impl PaletteChoice {
+    fn label(&self) -> &str {
+        match self {
+            Self::FollowTheme => "Follow theme",
+            Self::Dark => "Dark",
+            Self::Light => "Light",
+            Self::Preset(preset) => preset.name(),
+            Self::Saved(name) => name,
+        }
+    }
+}
+
+/// Which parts of the app are shown.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct PanelVisibility {
+    pub clock: bool,
+    pub date: bool,
+    pub digital_time: bool,
+    pub converter: bool,
+    pub reference_chart: bool,
+    pub glyph_builder: bool,
+    pub world_clock: bool,
+    pub stopwatch: bool,
+    pub countdown: bool,
+    pub alarms: bool,
+    pub palette_editor: bool,
+}
+
+impl Default for PanelVisibility {
+    fn default() -> Self {
+        Self {
+            clock: true,
+            date: true,
+            digital_time: true,
+            converter: true,
+            reference_chart: true,
+            glyph_builder: false,
+            world_clock: false,
+            stopwatch: false,
+            countdown: false,
+            alarms: false,
+            palette_editor: false,
+        }
+    }
+}
+
+/// User preferences, persisted with the rest of the app state.
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Format version of the stored settings, used to migrate state saved by older releases.
+    /// Missing from state saved before settings existed, so reads as 0 there.
+    #[serde(default)]
+    pub version: u32,
+
+    /// IANA name of the zone the main clock shows, or `None` for the system's local zone.
+    pub time_zone: Option<String>,
+
+    /// IANA names of the zones in the world clock, in display order.
+    pub world_clock_zones: Vec<String>,
+
+    pub hour_format: HourFormat,
+
+    /// Only used with [`HourFormat::TwelveHour`].
+    pub meridiem_marker: MeridiemMarker,
+
+    pub show_seconds: bool,
+
+    /// Repainting stays at once a second unless this is on.
+    pub sub_second: SubSecond,
+    pub transition: TransitionOptions,
+    pub date: DateOptions,
+    pub countdown: CountdownOptions,
+
+    /// Scale of the clock glyphs, where 1.0 draws a 34 pixel glyph. The main clock ignores it
+    /// while `fit_clock_to_window` is on.
+    pub glyph_scale: f32,
+
+    /// Grow or shrink the main clock to fill the window.
+    pub fit_clock_to_window: bool,
+
+    pub palette: PaletteChoice,
+
+    /// Draw each kind of stroke with its own dash pattern.
+    pub patterned_strokes: bool,
+
+    pub stroke: StrokeStyle,
+    pub orientation: Orientation,
+
+    /// Draw each reference chart numeral in both orientations, side by side.
+    pub chart_both_orientations: bool,
+
+    /// Palettes made in the palette editor or imported, in the order they were saved.
+    pub palettes: Vec<Palette>,
+
+    /// Show the Arabic value next to glyphs in the converter and reference chart.
+    pub show_arabic_captions: bool,
+
+    pub panels: PanelVisibility,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            version: Self::VERSION,
+            time_zone: None,
+            world_clock_zones: ["UTC", "America/New_York", "Europe/London", "Asia/Tokyo"]
+                .map(str::to_owned)
+                .to_vec(),
+            hour_format: HourFormat::default(),
+            meridiem_marker: MeridiemMarker::default(),
+            show_seconds: true,
+            sub_second: SubSecond::default(),
+            transition: TransitionOptions::default(),
+            date: DateOptions::default(),
+            countdown: CountdownOptions::default(),
+            glyph_scale: 4.0,
+            fit_clock_to_window: true,
+            palette: PaletteChoice::default(),
+            patterned_strokes: false,
+            stroke: StrokeStyle::default(),
+            orientation: Orientation::default(),
+            chart_both_orientations: false,
+            palettes: Vec::new(),
+            show_arabic_captions: true,
+            panels: PanelVisibility::default(),
+        }
+    }
+}
+
+impl Settings {
+    /// Current format version. Bump it, and add a step to [`Settings::migrate`], when a change
+    /// needs more than new fields with defaults.
+    pub const VERSION: u32 = 1;
+
+    pub const GLYPH_SCALE_RANGE: core::ops::RangeInclusive<f32> = 1.0..=12.0;
+
+    /// Settings for state saved before settings existed, so [`Settings::migrate`] runs on them.
+    pub fn unversioned() -> Self {
+        Self {
+            version: 0,
+            ..Default::default()
+        }
+    }
+
+    /// Brings settings saved by an older release up to [`Settings::VERSION`].
+    pub fn migrate(mut self) -> Self {
+        if self.version == 0 {
+            // Version 0: the app stored only template placeholders, so the defaults stand.
+            self.version = 1;
+        }
+        self
+    }
+
+    /// Replaces values which read but are outside their ranges, such as from hand-edited state,
+    /// with the defaults. Unlike [`Self::migrate`], this is needed whatever version the settings
+    /// were saved at.
+    pub fn sanitise(mut self) -> Self {
+        if !Self::GLYPH_SCALE_RANGE.contains(&self.glyph_scale) {
+            self.glyph_scale = Self::default().glyph_scale;
+        }
+        if !TransitionOptions::DURATION_RANGE.contains(&self.transition.duration_seconds) {
+            self.transition.duration_seconds = TransitionOptions::default().duration_seconds;
+        }
+        if !StrokeStyle::THICKNESS_RANGE.contains(&self.stroke.thickness) {
+            self.stroke.thickness = StrokeStyle::DEFAULT.thickness;
+        }
+        self
+    }
+
+    /// Deletes the saved palette at `index`, going back to following the theme if it was in use.
+    pub fn remove_palette(&mut self, index: usize) {
+        let palette = self.palettes.remove(index);
+        if self.palette == PaletteChoice::Saved(palette.name) {
+            self.palette = PaletteChoice::FollowTheme;
+        }
+    }
+
+    /// Colours of the chosen palette. A saved palette which no longer exists falls back to
+    /// following the theme.
+    pub fn colours(&self, dark_mode: bool) -> Colours {
+        let theme_colours = if dark_mode {
+            DARK_CISTERCIAN_NUMERAL_COLOURS
+        } else {
+            LIGHT_CISTERCIAN_NUMERAL_COLOURS
+        };
+        match &self.palette {
+            PaletteChoice::FollowTheme => theme_colours,
+            PaletteChoice::Dark => DARK_CISTERCIAN_NUMERAL_COLOURS,
+            PaletteChoice::Light => LIGHT_CISTERCIAN_NUMERAL_COLOURS,
+            PaletteChoice::Preset(preset) => preset.colours(dark_mode),
+            PaletteChoice::Saved(name) => self
+                .palettes
+                .iter()
+                .find(|palette| &palette.name == name)
+                .map_or(theme_colours, |palette| palette.colours),
+        }
+    }
+
+    /// How to draw glyphs with the chosen palette and stroke options.
+    pub fn glyph_style(&self, dark_mode: bool) -> GlyphStyle {
+        GlyphStyle {
+            colours: self.colours(dark_mode),
+            patterned: self.patterned_strokes,
+            stroke: self.stroke,
+            orientation: self.orientation,
+        }
+    }
+
+    pub fn ui(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Time zone:");
+            zone_picker(ui, "clock_time_zone", &mut self.time_zone, Some("Local"));
+        });
+        ui.horizontal(|ui| {
+            ui.label("Hours:");
+            ui.radio_value(&mut self.hour_format, HourFormat::TwentyFourHour, "24-hour");
+            ui.radio_value(&mut self.hour_format, HourFormat::TwelveHour, "12-hour");
+        });
+        if self.hour_format == HourFormat::TwelveHour {
+            ui.horizontal(|ui| {
+                ui.label("AM/PM:");
+                ui.radio_value(
+                    &mut self.meridiem_marker,
+                    MeridiemMarker::Caption,
+                    "Caption",
+                );
+                ui.radio_value(&mut self.meridiem_marker, MeridiemMarker::Dot, "Dot");
+            });
+        }
+        ui.checkbox(&mut self.show_seconds, "Show seconds");
+        ui.horizontal(|ui| {
+            ui.label("Fraction of a second:");
+            ui.radio_value(&mut self.sub_second, SubSecond::Off, "Off");
+            ui.radio_value(
+                &mut self.sub_second,
+                SubSecond::Centiseconds,
+                "Centiseconds",
+            );
+            ui.radio_value(
+                &mut self.sub_second,
+                SubSecond::Milliseconds,
+                "Milliseconds",
+            );
+        });
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.transition.enabled, "Animate changes");
+            ui.add_enabled_ui(self.transition.enabled, |ui| {
+                ui.radio_value(
+                    &mut self.transition.style,
+                    TransitionStyle::Retract,
+                    "Retract",
+                );
+                ui.radio_value(&mut self.transition.style, TransitionStyle::Fade, "Fade");
+                ui.add(
+                    Slider::new(
+                        &mut self.transition.duration_seconds,
+                        TransitionOptions::DURATION_RANGE,
+                    )
+                    .suffix(" s"),
+                );
+            });
+        });
+        ui.horizontal(|ui| {
+            ui.label("Month and day:");
+            ui.radio_value(
+                &mut self.date.month_day,
+                MonthDayStyle::Combined,
+                "One glyph",
+            );
+            ui.radio_value(
+                &mut self.date.month_day,
+                MonthDayStyle::Separate,
+                "Two glyphs",
+            );
+        });
+        ui.checkbox(&mut self.date.show_day_of_year, "Show day of year");
+        ui.checkbox(&mut self.date.show_iso_week, "Show ISO week");
+        ui.checkbox(&mut self.fit_clock_to_window, "Fit clock to window");
+        ui.add(Slider::new(&mut self.glyph_scale, Self::GLYPH_SCALE_RANGE).text("Clock scale"))
+            .on_hover_text("Also sets the size of glyphs in the other panels");
+        ui.horizontal(|ui| {
+            ui.label("Palette:");
+            ComboBox::from_id_salt("palette_choice")
+                .selected_text(self.palette.label())
+                .show_ui(ui, |ui| {
+                    let saved = self
+                        .palettes
+                        .iter()
+                        .map(|palette| PaletteChoice::Saved(palette.name.clone()));
+                    let presets = PalettePreset::ALL.map(PaletteChoice::Preset);
+                    let choices = [
+                        PaletteChoice::FollowTheme,
+                        PaletteChoice::Dark,
+                        PaletteChoice::Light,
+                    ]
+                    .into_iter()
+                    .chain(presets)
+                    .chain(saved);
+                    for choice in choices {
+                        let label = choice.label().to_owned();
+                        ui.selectable_value(&mut self.palette, choice, label);
+                    }
+                });
+        });
+        ui.checkbox(
+            &mut self.patterned_strokes,
+            "Dash pattern for each stroke kind",
+        );
+        ui.horizontal(|ui| {
+            ui.label("Pen:");
+            ui.radio_value(&mut self.stroke.pen, Pen::Uniform, "Uniform");
+            ui.radio_value(&mut self.stroke.pen, Pen::Manuscript, "Manuscript")
+                .on_hover_text("Broad and hairline strokes from an angled nib");
+        });
+        ui.add_enabled_ui(self.stroke.pen == Pen::Uniform, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Ends:");
+                ui.radio_value(&mut self.stroke.cap, LineCap::Butt, "Butt");
+                ui.radio_value(&mut self.stroke.cap, LineCap::Round, "Round");
+                ui.radio_value(&mut self.stroke.cap, LineCap::Square, "Square");
+            });
+            ui.horizontal(|ui| {
+                ui.label("Corners:");
+                ui.radio_value(&mut self.stroke.join, LineJoin::Miter, "Miter");
+                ui.radio_value(&mut self.stroke.join, LineJoin::Round, "Round");
+            });
+        });
+        ui.add(
+            Slider::new(&mut self.stroke.thickness, StrokeStyle::THICKNESS_RANGE)
+                .text("Stroke thickness"),
+        );
+        ui.horizontal(|ui| {
+            ui.label("Stem:");
+            ui.radio_value(&mut self.orientation, Orientation::Vertical, "Vertical");
+            ui.radio_value(&mut self.orientation, Orientation::Horizontal, "Horizontal")
+                .on_hover_text("The form found in some medieval manuscripts");
+        });
+        ui.checkbox(
+            &mut self.chart_both_orientations,
+            "Both orientations in the reference chart",
+        );
+        ui.checkbox(&mut self.show_arabic_captions, "Arabic numeral captions");
+        ui.separator();
+        if ui.button("Reset to defaults").clicked() {
+            // Saved palettes are the user's work rather than preferences, so they stay.
+            *self = Self {
+                palettes: core::mem::take(&mut self.palettes),
+                ..Self::default()
+            };
+        }
+    }
+}
+
+impl PanelVisibility {
+    /// Whether the clock is the only panel in the main area, so it can take all of it.
+    pub fn clock_only(&self) -> bool {
+        let Self {
+            clock,
+            date,
+            digital_time,
+            converter,
+            reference_chart,
+            glyph_builder: _,
+            world_clock,
+            stopwatch,
+            countdown,
+            alarms,
+            palette_editor,
+        } = *self;
+        clock
+            && ![
+                date,
+                digital_time,
+                converter,
+                reference_chart,
+                world_clock,
+                stopwatch,
+                countdown,
+                alarms,
+                palette_editor,
+            ]
+            .contains(&true)
+    }
+
+    pub fn ui(&mut self, ui: &mut Ui) {
+        ui.checkbox(&mut self.clock, "Cistercian clock");
+        ui.checkbox(&mut self.date, "Date");
+        ui.checkbox(&mut self.digital_time, "Digital time");
+        ui.checkbox(&mut self.converter, "Number converter");
+        ui.checkbox(&mut self.reference_chart, "Reference chart");
+        ui.checkbox(&mut self.world_clock, "World clock");
+        ui.checkbox(&mut self.stopwatch, "Stopwatch");
+        ui.checkbox(&mut self.countdown, "Countdown");
+        ui.checkbox(&mut self.alarms, "Alarms");
+        ui.checkbox(&mut self.palette_editor, "Palette editor");
+        ui.checkbox(&mut self.glyph_builder, "Glyph builder");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::CistercianClockApp;
+
+    fn saved(name: &str) -> Palette {
+        Palette {
+            name: name.to_owned(),
+            colours: DARK_CISTERCIAN_NUMERAL_COLOURS,
+        }
+    }
+
+    #[test]
+    fn removing_palette_in_use_follows_theme() {
+        let mut settings = Settings {
+            palette: PaletteChoice::Saved("Second".to_owned()),
+            palettes: vec![saved("First"), saved("Second")],
+            ..Default::default()
+        };
+        settings.remove_palette(1);
+        assert_eq!(settings.palettes, [saved("First")]);
+        assert_eq!(settings.palette, PaletteChoice::FollowTheme);
+    }
+
+    #[test]
+    fn removing_other_palette_keeps_choice() {
+        let choice = PaletteChoice::Saved("Second".to_owned());
+        let mut settings = Settings {
+            palette: choice.clone(),
+            palettes: vec![saved("First"), saved("Second")],
+            ..Default::default()
+        };
+        settings.remove_palette(0);
+        assert_eq!(settings.palettes, [saved("Second")]);
+        assert_eq!(settings.palette, choice);
+    }
+
+    #[test]
+    fn migrates_unversioned_settings() {
+        let migrated = Settings::unversioned().migrate();
+        assert_eq!(migrated, Settings::default());
+    }
+
+    #[test]
+    fn migrates_template_state() {
+        // The template app saved only its label, with no settings at all.
+        let app: CistercianClockApp = serde_json::from_str(r#"{"label": "Hello World!"}"#).unwrap();
+        assert_eq!(app.settings, Settings::unversioned());
+        assert_eq!(app.settings.migrate(), Settings::default());
+    }
+
+    #[test]
+    fn reads_saved_state() {
+        let app = CistercianClockApp {
+            converter_input: "2025".to_owned(),
+            settings: Settings {
+                palettes: vec![saved("Mine")],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let saved = ron::to_string(&app).unwrap();
+        let read = CistercianClockApp::from_saved(&saved);
+        assert_eq!(read.converter_input, "2025");
+        assert_eq!(read.settings, app.settings);
+
+        // A hand-edited colour which is not valid hex.
+        let colour = DARK_CISTERCIAN_NUMERAL_COLOURS.colour_0.to_hex();
+        let unreadable = saved.replacen(&colour, "#12345g", 1);
+        assert_ne!(unreadable, saved);
+        let read = CistercianClockApp::from_saved(&unreadable);
+        assert_eq!(
+            read.converter_input,
+            CistercianClockApp::default().converter_input
+        );
+    }
+
+    #[test]
+    fn sanitises_current_version() {
+        let settings = Settings {
+            glyph_scale: 100.0,
+            transition: TransitionOptions {
+                duration_seconds: 5.0,
+                ..Default::default()
+            },
+            stroke: StrokeStyle {
+                thickness: 0.0,
+                ..StrokeStyle::DEFAULT
+            },
+            ..Default::default()
+        };
+        assert_eq!(settings.migrate().sanitise(), Settings::default());
+    }
+
+    #[test]
+    fn keeps_values_in_range() {
+        let settings = Settings {
+            glyph_scale: 2.5,
+            transition: TransitionOptions {
+                duration_seconds: 0.9,
+                ..Default::default()
+            },
+            stroke: StrokeStyle {
+                thickness: 3.0,
+                ..StrokeStyle::DEFAULT
+            },
+            ..Default::default()
+        };
+        assert_eq!(settings.clone().sanitise(), settings);
+    }
+}
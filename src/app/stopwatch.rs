@@ -0,0 +1,200 @@
+use super::{paint_number, wrapping_numeral, CistercianClockApp, GlyphStyle};
+use crate::CistercianNumeral;
+use core::time::Duration;
+use egui::{RichText, Ui};
+
+/// Scale of the lap glyphs, relative to the running time.
+const LAP_SCALE: f32 = 1.5;
+
+/// Stopwatch timed from egui's monotonic input clock rather than the wall clock, so changes to
+/// the system time do not affect it.
+#[derive(Debug, Default)]
+pub(super) struct Stopwatch {
+    /// Input time when the stopwatch was last started, while it is running.
+    started_at: Option<Duration>,
+
+    /// Time accumulated before the last start.
+    accumulated: Duration,
+
+    /// Total elapsed time at each lap, oldest first.
+    laps: Vec<Duration>,
+}
+
+impl Stopwatch {
+    pub(super) fn is_running(&self) -> bool {
+        self.started_at.is_some()
+    }
+
+    pub(super) fn elapsed(&self, now: Duration) -> Duration {
+        self.accumulated
+            + self
+                .started_at
+                .map_or(Duration::ZERO, |started_at| now.saturating_sub(started_at))
+    }
+
+    pub(super) fn start(&mut self, now: Duration) {
+        self.started_at.get_or_insert(now);
+    }
+
+    pub(super) fn stop(&mut self, now: Duration) {
+        self.accumulated = self.elapsed(now);
+        self.started_at = None;
+    }
+
+    pub(super) fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    pub(super) fn lap(&mut self, now: Duration) {
+        self.laps.push(self.elapsed(now));
+    }
+
+    /// Length of each lap, paired with the total elapsed time when it ended.
+    fn lap_times(&self) -> impl Iterator<Item = (Duration, Duration)> + '_ {
+        let starts = core::iter::once(Duration::ZERO).chain(self.laps.iter().copied());
+        self.laps
+            .iter()
+            .zip(starts)
+            .map(|(&end, start)| (end - start, end))
+    }
+}
+
+/// Whole hundredths of a second in `duration`.
+fn hundredths(duration: Duration) -> u64 {
+    duration.as_secs() * 100 + u64::from(duration.subsec_millis() / 10)
+}
+
+/// Minutes, seconds and hundredths of `duration`, with minutes wrapping after 9,999.
+fn minutes_seconds_hundredths(
+    duration: Duration,
+) -> (CistercianNumeral, CistercianNumeral, CistercianNumeral) {
+    let hundredths = hundredths(duration);
+    (
+        wrapping_numeral(hundredths / 6_000),
+        wrapping_numeral(hundredths / 100 % 60),
+        wrapping_numeral(hundredths % 100),
+    )
+}
+
+/// Arabic form of `duration`, such as `01:23.45`.
+fn format_duration(duration: Duration) -> String {
+    let hundredths = hundredths(duration);
+    format!(
+        "{:02}:{:02}.{:02}",
+        hundredths / 6_000,
+        hundredths / 100 % 60,
+        hundredths % 100
+    )
+}
+
+fn paint_duration(ui: &mut Ui, style: &GlyphStyle, duration: Duration, scale: f32) {
+    let (minutes, seconds, hundredths) = minutes_seconds_hundredths(duration);
+    ui.horizontal(|ui| {
+        paint_number(ui, style, minutes, Some(scale), None);
+        paint_number(ui, style, seconds, Some(scale), None);
+        paint_number(ui, style, hundredths, Some(scale), None);
+    });
+}
+
+impl CistercianClockApp {
+    /// Stopwatch showing minutes, seconds and hundredths as glyphs, with a list of laps.
+    pub(super) fn stopwatch_ui(&mut self, ui: &mut Ui, style: &GlyphStyle) {
+        ui.heading("Stopwatch");
+        ui.add_space(15.0);
+
+        let now = Duration::from_secs_f64(ui.input(|input| input.time));
+        let stopwatch = &mut self.stopwatch;
+        if stopwatch.is_running() {
+            // Keep the hundredths glyph moving.
+            ui.ctx().request_repaint();
+        }
+
+        let elapsed = stopwatch.elapsed(now);
+        paint_duration(ui, style, elapsed, self.settings.glyph_scale);
+        ui.label(RichText::new(format_duration(elapsed)).monospace());
+        ui.add_space(10.0);
+
+        ui.horizontal(|ui| {
+            if stopwatch.is_running() {
+                if ui.button("Stop").clicked() {
+                    stopwatch.stop(now);
+                }
+                if ui.button("Lap").clicked() {
+                    stopwatch.lap(now);
+                }
+            } else {
+                if ui.button("Start").clicked() {
+                    stopwatch.start(now);
+                }
+                if ui.button("Reset").clicked() {
+                    stopwatch.reset();
+                }
+            }
+        });
+
+        if stopwatch.laps.is_empty() {
+            return;
+        }
+        ui.add_space(15.0);
+        let laps: Vec<_> = stopwatch.lap_times().enumerate().collect();
+        for (index, (lap, total)) in laps.into_iter().rev() {
+            ui.horizontal(|ui| {
+                ui.label(format!(
+                    "Lap {}: {} (total {})",
+                    index + 1,
+                    format_duration(lap),
+                    format_duration(total)
+                ));
+                paint_duration(ui, style, lap, LAP_SCALE);
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    fn values(duration: Duration) -> (u16, u16, u16) {
+        let (minutes, seconds, hundredths) = minutes_seconds_hundredths(duration);
+        (minutes.value(), seconds.value(), hundredths.value())
+    }
+
+    #[test]
+    fn laps_split_totals() {
+        let mut stopwatch = Stopwatch::default();
+        stopwatch.start(millis(1_000));
+        stopwatch.lap(millis(3_500));
+        stopwatch.stop(millis(4_000));
+        stopwatch.start(millis(10_000));
+        stopwatch.lap(millis(12_250));
+        assert_eq!(stopwatch.elapsed(millis(13_000)), millis(6_000));
+        assert_eq!(
+            stopwatch.lap_times().collect::<Vec<_>>(),
+            [
+                (millis(2_500), millis(2_500)),
+                (millis(2_750), millis(5_250))
+            ]
+        );
+    }
+
+    #[test]
+    fn seconds_roll_over_into_minutes() {
+        assert_eq!(values(millis(59_990)), (0, 59, 99));
+        assert_eq!(format_duration(millis(59_999)), "00:59.99");
+        assert_eq!(values(millis(60_000)), (1, 0, 0));
+        assert_eq!(format_duration(millis(60_000)), "01:00.00");
+    }
+
+    #[test]
+    fn minutes_wrap_after_9999() {
+        let minute = 60_000;
+        assert_eq!(values(millis(9_999 * minute + 1_230)), (9_999, 1, 23));
+        assert_eq!(values(millis(10_000 * minute + 1_230)), (0, 1, 23));
+        assert_eq!(format_duration(millis(10_000 * minute)), "10000:00.00");
+    }
+}
@@ -0,0 +1,161 @@
+use super::{paint_number, settings::HourFormat, CistercianClockApp, GlyphStyle};
+use crate::glyph;
+use chrono::{DateTime, FixedOffset, Local, Utc};
+use chrono_tz::{Tz, TZ_VARIANTS};
+use egui::{ComboBox, Grid, RichText, Ui};
+
+/// Scale of the glyphs in each world clock row.
+const WORLD_CLOCK_SCALE: f32 = 2.0;
+
+/// Current time in `zone`, an IANA zone name, or in the local zone when there is no zone. `None`
+/// when the zone is not in the bundled database, such as a misspelt name in hand-edited state.
+pub(super) fn now_in_zone(zone: Option<&str>) -> Option<DateTime<FixedOffset>> {
+    match zone {
+        Some(zone) => {
+            let tz: Tz = zone.parse().ok()?;
+            Some(Utc::now().with_timezone(&tz).fixed_offset())
+        }
+        None => Some(Local::now().fixed_offset()),
+    }
+}
+
+/// Inline error for a stored zone which is not in the bundled database.
+pub(super) fn unknown_zone_label(ui: &mut Ui) {
+    ui.label(RichText::new("Unknown zone").color(ui.visuals().error_fg_color));
+}
+
+/// Offset in the form `UTC+05:30`.
+pub(super) fn utc_offset_label(offset: &FixedOffset) -> String {
+    let seconds = offset.local_minus_utc();
+    let sign = if seconds < 0 { '-' } else { '+' };
+    let minutes = seconds.abs() / 60;
+    format!("UTC{sign}{:02}:{:02}", minutes / 60, minutes % 60)
+}
+
+/// Drop-down of every zone in the bundled database. With `local_label`, the first entry clears
+/// the selection, standing for the system's local zone.
+pub(super) fn zone_picker(
+    ui: &mut Ui,
+    id_salt: &str,
+    selected: &mut Option<String>,
+    local_label: Option<&str>,
+) {
+    let text = selected
+        .as_deref()
+        .or(local_label)
+        .unwrap_or("Choose a zone")
+        .to_owned();
+    ComboBox::from_id_salt(id_salt)
+        .selected_text(text)
+        .height(300.0)
+        .show_ui(ui, |ui| {
+            if let Some(local_label) = local_label {
+                ui.selectable_value(selected, None, local_label);
+            }
+            for tz in TZ_VARIANTS {
+                let name = tz.name();
+                let is_selected = selected.as_deref() == Some(name);
+                if ui.selectable_label(is_selected, name).clicked() {
+                    *selected = Some(name.to_owned());
+                }
+            }
+        });
+}
+
+impl CistercianClockApp {
+    /// Cistercian time in each of the user's chosen zones, with controls to edit the list.
+    pub(super) fn world_clock_ui(&mut self, ui: &mut Ui, style: &GlyphStyle) {
+        ui.heading("World Clock");
+        ui.add_space(15.0);
+
+        let hour_format = self.settings.hour_format;
+        let mut removed = None;
+        Grid::new("world_clock")
+            .num_columns(4)
+            .spacing([24.0, 12.0])
+            .show(ui, |ui| {
+                for (index, zone) in self.settings.world_clock_zones.iter().enumerate() {
+                    let Some(now) = now_in_zone(Some(zone)) else {
+                        ui.label(zone);
+                        unknown_zone_label(ui);
+                        ui.label("");
+                        if ui.button("Remove").clicked() {
+                            removed = Some(index);
+                        }
+                        ui.end_row();
+                        continue;
+                    };
+                    let (hours_minutes, seconds) =
+                        glyph::time_numerals(now.time(), hour_format == HourFormat::TwelveHour);
+                    ui.vertical(|ui| {
+                        ui.label(zone);
+                        ui.label(RichText::new(utc_offset_label(now.offset())).small());
+                    });
+                    ui.horizontal(|ui| {
+                        paint_number(ui, style, hours_minutes, Some(WORLD_CLOCK_SCALE), None);
+                        paint_number(ui, style, seconds, Some(WORLD_CLOCK_SCALE), None);
+                    });
+                    ui.label(match hour_format {
+                        HourFormat::TwentyFourHour => now.format("%H:%M %S").to_string(),
+                        HourFormat::TwelveHour => now.format("%-I:%M %S %p").to_string(),
+                    });
+                    if ui.button("Remove").clicked() {
+                        removed = Some(index);
+                    }
+                    ui.end_row();
+                }
+            });
+        if let Some(index) = removed {
+            self.settings.world_clock_zones.remove(index);
+        }
+
+        ui.add_space(15.0);
+        ui.horizontal(|ui| {
+            zone_picker(
+                ui,
+                "world_clock_new_zone",
+                &mut self.new_world_clock_zone,
+                None,
+            );
+            let can_add = self
+                .new_world_clock_zone
+                .as_ref()
+                .is_some_and(|zone| !self.settings.world_clock_zones.contains(zone));
+            if ui.add_enabled(can_add, egui::Button::new("Add")).clicked() {
+                if let Some(zone) = self.new_world_clock_zone.take() {
+                    self.settings.world_clock_zones.push(zone);
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_zones_in_database() {
+        let now = now_in_zone(Some("UTC")).unwrap();
+        assert_eq!(now.offset().local_minus_utc(), 0);
+        assert!(now_in_zone(Some("Asia/Tokyo")).is_some());
+        assert!(now_in_zone(None).is_some());
+    }
+
+    #[test]
+    fn rejects_unknown_zone() {
+        assert_eq!(now_in_zone(Some("Europe/Atlantis")), None);
+        assert_eq!(now_in_zone(Some("")), None);
+    }
+
+    #[test]
+    fn labels_offsets() {
+        let offset = |seconds| FixedOffset::east_opt(seconds).unwrap();
+        assert_eq!(utc_offset_label(&offset(0)), "UTC+00:00");
+        assert_eq!(utc_offset_label(&offset(5 * 3_600 + 30 * 60)), "UTC+05:30");
+        assert_eq!(
+            utc_offset_label(&offset(-(3 * 3_600 + 30 * 60))),
+            "UTC-03:30"
+        );
+    }
+}
@@ -0,0 +1,604 @@
+#![warn(clippy::all, rust_2018_idioms)]
+
+//! Renders Cistercian numerals to SVG or PNG from the command line, without opening a window.
+
+use chrono::Local;
+use cistercian_clock::{
+    glyph::{Grouping, Orientation},
+    palette::{
+        Palette, PaletteFormat, PalettePreset, DARK_CISTERCIAN_NUMERAL_COLOURS,
+        LIGHT_CISTERCIAN_NUMERAL_COLOURS,
+    },
+    raster::{self, Background},
+    style::{GlyphStyle, LineCap, LineJoin, Pen, StrokeStyle},
+    svg,
+    transition::TransitionStyle,
+    CistercianNumeral, CompoundNumeral,
+};
+use core::ops::Range;
+use ecolor::Color32;
+use image::{ImageFormat, RgbaImage};
+use std::{
+    io::{Cursor, Write},
+    path::PathBuf,
+    process::ExitCode,
+};
+
+const USAGE: &str = "Usage:
+  cistercian render <NUMBER> [--grouping <positional|bracketed>] [OPTIONS]
+  cistercian now [OPTIONS]
+  cistercian chart [--range <START>..<END>] [OPTIONS]
+  cistercian animate <FROM> <TO> --out <DIRECTORY> [--frames <COUNT>]
+                     [--transition <retract|fade>] [OPTIONS]
+
+Options:
+  --format <svg|png>     Output format (default: svg)
+  --scale <SCALE>        Glyph scale, where 1 draws a 34 pixel glyph (default: 4)
+  --theme <light|dark>   Palette, light draws dark strokes for a light page (default: light)
+  --palette <FILE>       Palette exported from the app, as .json or .toml, used
+                         in place of --preset and --theme colours
+  --preset <NAME>        Built-in palette, in its variant for --theme: deuteranopia,
+                         protanopia, tritanopia or high-contrast
+  --orientation <vertical|horizontal>
+                         Direction of the stem (default: vertical)
+  --patterned            Dash each kind of stroke in its own pattern
+  --cap <butt|round|square>
+                         Finish for the free ends of strokes (default: butt)
+  --join <miter|round>   Corner where strokes meet (default: miter)
+  --thickness <FACTOR>   Stroke width relative to the usual width for the
+                         scale, from 0.5 to 3 (default: 1)
+  --pen <uniform|manuscript>
+                         Even strokes, or broad and hairline strokes from an
+                         angled nib (default: uniform)
+  --background <RRGGBB>  Solid PNG background colour (default: transparent)
+  --out <PATH>           File to write (default: standard output), or the
+                         directory for `animate` frames
+
+Numbers above 9,999 are drawn with one glyph per group of four digits. With
+positional grouping the glyphs read left to right like Arabic digits, while
+bracketed grouping marks each glyph with one bar per power of 10,000.
+
+`animate` writes each frame of the change from one glyph to the next as
+frame-000.svg (or .png) and so on, with 12 frames by default.";
+
+/// Most pixels a PNG may have, about 256 MiB of image data, so a large `--scale` is refused
+/// rather than exhausting memory.
+const MAX_PIXELS: u64 = 64 * 1024 * 1024;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Format {
+    Svg,
+    Png,
+}
+
+enum Command {
+    Render(CompoundNumeral, Grouping),
+    Now,
+    Chart(Range<u32>),
+    Animate {
+        from: CistercianNumeral,
+        to: CistercianNumeral,
+        transition: TransitionStyle,
+        frames: usize,
+    },
+}
+
+struct Options {
+    format: Format,
+    scale: f32,
+    style: GlyphStyle,
+    background: Background,
+    out: Option<PathBuf>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            format: Format::Svg,
+            scale: 4.0,
+            style: GlyphStyle::new(LIGHT_CISTERCIAN_NUMERAL_COLOURS),
+            background: Background::Transparent,
+            out: None,
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.is_empty() || args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        println!("{USAGE}");
+        return ExitCode::SUCCESS;
+    }
+
+    match parse_args(&args).and_then(|(command, options)| run(&command, &options)) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("error: {message}\n\n{USAGE}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn parse_args(args: &[String]) -> Result<(Command, Options), String> {
+    let mut args = args.iter();
+    let mut command = match args.next().map(String::as_str) {
+        Some("render") => {
+            let number = args.next().ok_or("`render` needs a number")?;
+            let number = number
+                .parse::<CompoundNumeral>()
+                .map_err(|error| format!("invalid number `{number}`: {error}"))?;
+            Command::Render(number, Grouping::default())
+        }
+        Some("now") => Command::Now,
+        Some("chart") => Command::Chart(0..100),
+        Some("animate") => {
+            let mut numeral = || {
+                let number = args.next().ok_or("`animate` needs two numbers")?;
+                number
+                    .parse::<CistercianNumeral>()
+                    .map_err(|error| format!("invalid number `{number}`: {error}"))
+            };
+            Command::Animate {
+                from: numeral()?,
+                to: numeral()?,
+                transition: TransitionStyle::default(),
+                frames: 12,
+            }
+        }
+        Some(other) => return Err(format!("unknown command `{other}`")),
+        None => return Err("missing command".to_owned()),
+    };
+
+    let mut options = Options::default();
+    let mut dark_theme = false;
+    let mut preset = None;
+    let mut palette = None;
+    while let Some(flag) = args.next() {
+        let mut value = || args.next().ok_or_else(|| format!("`{flag}` needs a value"));
+        match flag.as_str() {
+            "--format" => {
+                options.format = match value()?.as_str() {
+                    "svg" => Format::Svg,
+                    "png" => Format::Png,
+                    other => return Err(format!("unknown format `{other}`")),
+                }
+            }
+            "--scale" => {
+                let scale = value()?;
+                options.scale = scale
+                    .parse::<f32>()
+                    .ok()
+                    .filter(|scale| scale.is_finite() && *scale > 0.0)
+                    .ok_or_else(|| format!("invalid scale `{scale}`"))?;
+            }
+            "--theme" => {
+                dark_theme = match value()?.as_str() {
+                    "light" => false,
+                    "dark" => true,
+                    other => return Err(format!("unknown theme `{other}`")),
+                };
+            }
+            "--preset" => {
+                preset = Some(match value()?.as_str() {
+                    "deuteranopia" => PalettePreset::Deuteranopia,
+                    "protanopia" => PalettePreset::Protanopia,
+                    "tritanopia" => PalettePreset::Tritanopia,
+                    "high-contrast" => PalettePreset::HighContrast,
+                    other => return Err(format!("unknown preset `{other}`")),
+                });
+            }
+            "--palette" => {
+                let path = PathBuf::from(value()?);
+                let format = path
+                    .extension()
+                    .and_then(|extension| extension.to_str())
+                    .and_then(PaletteFormat::from_extension)
+                    .ok_or_else(|| {
+                        format!("`{}` should be a .json or .toml palette", path.display())
+                    })?;
+                let text = std::fs::read_to_string(&path)
+                    .map_err(|error| format!("unable to read `{}`: {error}", path.display()))?;
+                palette = Some(
+                    Palette::import(&text, format)
+                        .map_err(|error| format!("`{}`: {error}", path.display()))?,
+                );
+            }
+            "--orientation" => {
+                options.style.orientation = match value()?.as_str() {
+                    "vertical" => Orientation::Vertical,
+                    "horizontal" => Orientation::Horizontal,
+                    other => return Err(format!("unknown orientation `{other}`")),
+                }
+            }
+            "--patterned" => options.style.patterned = true,
+            "--cap" => {
+                options.style.stroke.cap = match value()?.as_str() {
+                    "butt" => LineCap::Butt,
+                    "round" => LineCap::Round,
+                    "square" => LineCap::Square,
+                    other => return Err(format!("unknown cap `{other}`")),
+                }
+            }
+            "--join" => {
+                options.style.stroke.join = match value()?.as_str() {
+                    "miter" => LineJoin::Miter,
+                    "round" => LineJoin::Round,
+                    other => return Err(format!("unknown join `{other}`")),
+                }
+            }
+            "--thickness" => {
+                let thickness = value()?;
+                options.style.stroke.thickness = thickness
+                    .parse::<f32>()
+                    .ok()
+                    .filter(|thickness| StrokeStyle::THICKNESS_RANGE.contains(thickness))
+                    .ok_or_else(|| {
+                        format!(
+                            "invalid thickness `{thickness}`, expected {} to {}",
+                            StrokeStyle::THICKNESS_RANGE.start(),
+                            StrokeStyle::THICKNESS_RANGE.end()
+                        )
+                    })?;
+            }
+            "--pen" => {
+                options.style.stroke.pen = match value()?.as_str() {
+                    "uniform" => Pen::Uniform,
+                    "manuscript" => Pen::Manuscript,
+                    other => return Err(format!("unknown pen `{other}`")),
+                }
+            }
+            "--background" => {
+                let hex = value()?;
+                let colour = Color32::from_hex(&format!("#{}", hex.trim_start_matches('#')))
+                    .map_err(|_| format!("invalid background colour `{hex}`"))?;
+                options.background = Background::Solid(colour);
+            }
+            "--out" => options.out = Some(PathBuf::from(value()?)),
+            "--grouping" => {
+                let Command::Render(_, grouping) = &mut command else {
+                    return Err("`--grouping` only applies to `render`".to_owned());
+                };
+                *grouping = match value()?.as_str() {
+                    "positional" => Grouping::Positional,
+                    "bracketed" => Grouping::Bracketed,
+                    other => return Err(format!("unknown grouping `{other}`")),
+                }
+            }
+            "--range" => {
+                let Command::Chart(range) = &mut command else {
+                    return Err("`--range` only applies to `chart`".to_owned());
+                };
+                *range = parse_range(value()?)?;
+            }
+            "--frames" => {
+                let Command::Animate { frames, .. } = &mut command else {
+                    return Err("`--frames` only applies to `animate`".to_owned());
+                };
+                let count = value()?;
+                *frames = count
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|count| *count > 0)
+                    .ok_or_else(|| format!("invalid frame count `{count}`"))?;
+            }
+            "--transition" => {
+                let Command::Animate { transition, .. } = &mut command else {
+                    return Err("`--transition` only applies to `animate`".to_owned());
+                };
+                *transition = match value()?.as_str() {
+                    "retract" => TransitionStyle::Retract,
+                    "fade" => TransitionStyle::Fade,
+                    other => return Err(format!("unknown transition `{other}`")),
+                }
+            }
+            other => return Err(format!("unknown option `{other}`")),
+        }
+    }
+
+    // Whatever the order of the flags, a palette file wins over a preset, which wins over the
+    // theme's own colours.
+    options.style.colours = match (palette, preset) {
+        (Some(palette), _) => palette.colours,
+        (None, Some(preset)) => preset.colours(dark_theme),
+        (None, None) if dark_theme => DARK_CISTERCIAN_NUMERAL_COLOURS,
+        (None, None) => LIGHT_CISTERCIAN_NUMERAL_COLOURS,
+    };
+    if matches!(command, Command::Animate { .. }) && options.out.is_none() {
+        return Err("`animate` needs an `--out` directory for its frames".to_owned());
+    }
+    Ok((command, options))
+}
+
+/// Parses an exclusive range written as `START..END`.
+fn parse_range(range: &str) -> Result<Range<u32>, String> {
+    let invalid = || format!("invalid range `{range}`, expected for example `0..100`");
+    let (start, end) = range.split_once("..").ok_or_else(invalid)?;
+    let start = start.trim().parse::<u32>().map_err(|_| invalid())?;
+    let end = end.trim().parse::<u32>().map_err(|_| invalid())?;
+    if start >= end {
+        return Err(invalid());
+    }
+    Ok(start..end)
+}
+
+fn run(command: &Command, options: &Options) -> Result<(), String> {
+    let Options {
+        format,
+        scale,
+        style,
+        background,
+        ..
+    } = options;
+    let (scale, time) = (*scale, Local::now().time());
+    if let Command::Animate {
+        from,
+        to,
+        transition,
+        frames,
+    } = *command
+    {
+        return write_frames(options, from, to, transition, frames);
+    }
+
+    match format {
+        Format::Svg => {
+            let document = match command {
+                Command::Render(number, grouping) => {
+                    svg::compound_svg(*number, *grouping, scale, style)
+                }
+                Command::Now => svg::time_svg(time, scale, style),
+                Command::Chart(range) => svg::chart_svg(range.clone(), scale, style),
+                Command::Animate { .. } => unreachable!("animate writes frames above"),
+            };
+            write_output(options, document.as_bytes())
+        }
+        Format::Png => {
+            check_size(match command {
+                Command::Render(number, grouping) => {
+                    raster::compound_size(*number, *grouping, scale, style)
+                }
+                Command::Now => raster::time_size(scale, style),
+                Command::Chart(range) => raster::chart_size(range, scale, style),
+                Command::Animate { .. } => unreachable!("animate writes frames above"),
+            })?;
+            let image: RgbaImage = match command {
+                Command::Render(number, grouping) => {
+                    raster::compound_image(*number, *grouping, scale, style, *background)
+                }
+                Command::Now => raster::time_image(time, scale, style, *background),
+                Command::Chart(range) => {
+                    raster::chart_image(range.clone(), scale, style, *background)
+                }
+                Command::Animate { .. } => unreachable!("animate writes frames above"),
+            };
+            write_output(options, &encode_png(&image)?)
+        }
+    }
+}
+
+/// Writes each frame of an animation into the `--out` directory, creating it if needed.
+fn write_frames(
+    options: &Options,
+    from: CistercianNumeral,
+    to: CistercianNumeral,
+    transition: TransitionStyle,
+    frames: usize,
+) -> Result<(), String> {
+    let Options {
+        format,
+        scale,
+        style,
+        background,
+        out,
+    } = options;
+    let directory = out
+        .as_ref()
+        .ok_or("`animate` needs an `--out` directory for its frames")?;
+    std::fs::create_dir_all(directory)
+        .map_err(|error| format!("unable to create `{}`: {error}", directory.display()))?;
+
+    let files: Vec<Vec<u8>> = match format {
+        Format::Svg => svg::transition_svgs(from, to, transition, frames, *scale, style)
+            .into_iter()
+            .map(String::into_bytes)
+            .collect(),
+        Format::Png => {
+            check_size(raster::glyph_size(*scale, style))?;
+            raster::transition_images(from, to, transition, frames, *scale, style, *background)
+                .iter()
+                .map(encode_png)
+                .collect::<Result<_, _>>()?
+        }
+    };
+    let extension = match format {
+        Format::Svg => "svg",
+        Format::Png => "png",
+    };
+    for (index, bytes) in files.iter().enumerate() {
+        let path = directory.join(format!("frame-{index:03}.{extension}"));
+        std::fs::write(&path, bytes)
+            .map_err(|error| format!("unable to write `{}`: {error}", path.display()))?;
+    }
+    Ok(())
+}
+
+/// Refuses a PNG of `width` × `height` pixels when it is larger than [`MAX_PIXELS`].
+fn check_size((width, height): (u32, u32)) -> Result<(), String> {
+    if u64::from(width) * u64::from(height) > MAX_PIXELS {
+        return Err(format!(
+            "a {width} × {height} pixel image is too large, try a smaller `--scale`"
+        ));
+    }
+    Ok(())
+}
+
+fn encode_png(image: &RgbaImage) -> Result<Vec<u8>, String> {
+    let mut bytes = Vec::new();
+    image
+        .write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png)
+        .map_err(|error| format!("unable to encode PNG: {error}"))?;
+    Ok(bytes)
+}
+
+fn write_output(options: &Options, bytes: &[u8]) -> Result<(), String> {
+    match &options.out {
+        Some(path) => std::fs::write(path, bytes)
+            .map_err(|error| format!("unable to write `{}`: {error}", path.display())),
+        None => std::io::stdout()
+            .write_all(bytes)
+            .map_err(|error| format!("unable to write output: {error}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<(Command, Options), String> {
+        let args: Vec<String> = args.iter().map(|&arg| arg.to_owned()).collect();
+        parse_args(&args)
+    }
+
+    fn error(args: &[&str]) -> String {
+        match parse(args) {
+            Ok(_) => panic!("{args:?} should be rejected"),
+            Err(message) => message,
+        }
+    }
+
+    #[test]
+    fn parses_render_options() {
+        let (command, options) = parse(&[
+            "render",
+            "1,984",
+            "--grouping",
+            "bracketed",
+            "--format",
+            "png",
+            "--scale",
+            "2",
+        ])
+        .unwrap();
+        assert!(matches!(
+            command,
+            Command::Render(number, Grouping::Bracketed) if number.value() == 1_984
+        ));
+        assert_eq!(options.format, Format::Png);
+        assert_eq!(options.scale, 2.0);
+    }
+
+    #[test]
+    fn rejects_unknown_flag() {
+        assert_eq!(error(&["now", "--colour"]), "unknown option `--colour`");
+    }
+
+    #[test]
+    fn rejects_option_for_another_command() {
+        assert_eq!(
+            error(&["chart", "--grouping", "bracketed"]),
+            "`--grouping` only applies to `render`"
+        );
+        assert_eq!(
+            error(&["render", "5", "--range", "0..10"]),
+            "`--range` only applies to `chart`"
+        );
+    }
+
+    #[test]
+    fn rejects_missing_value() {
+        assert_eq!(error(&["now", "--scale"]), "`--scale` needs a value");
+    }
+
+    #[test]
+    fn rejects_invalid_scale() {
+        for scale in ["0", "-1", "inf", "NaN", "big"] {
+            assert_eq!(
+                error(&["now", "--scale", scale]),
+                format!("invalid scale `{scale}`")
+            );
+        }
+    }
+
+    #[test]
+    fn animate_needs_out() {
+        assert_eq!(
+            error(&["animate", "1", "2"]),
+            "`animate` needs an `--out` directory for its frames"
+        );
+        assert!(parse(&["animate", "1", "2", "--out", "frames"]).is_ok());
+    }
+
+    #[test]
+    fn parses_ranges() {
+        assert_eq!(parse_range("5..15"), Ok(5..15));
+        assert_eq!(parse_range(" 0 .. 100 "), Ok(0..100));
+        for range in ["5..5", "15..5", "5", "..5", "a..b"] {
+            assert!(parse_range(range).is_err(), "{range}");
+        }
+    }
+
+    #[test]
+    fn refuses_huge_images() {
+        let style = GlyphStyle::default();
+        let number = CompoundNumeral::new(1_984);
+        assert!(check_size(raster::compound_size(
+            number,
+            Grouping::default(),
+            4.0,
+            &style
+        ))
+        .is_ok());
+        assert!(check_size(raster::compound_size(
+            number,
+            Grouping::default(),
+            1_000_000.0,
+            &style
+        ))
+        .is_err());
+    }
+
+    #[test]
+    fn palette_wins_over_preset_and_theme() {
+        let palette = Palette {
+            name: "Mine".to_owned(),
+            colours: PalettePreset::Tritanopia.colours(false),
+        };
+        let path = std::env::temp_dir().join(format!("cistercian-{}.json", std::process::id()));
+        std::fs::write(&path, palette.export(PaletteFormat::Json)).unwrap();
+        let path = path.to_str().unwrap();
+
+        for args in [
+            [
+                "--palette",
+                path,
+                "--theme",
+                "dark",
+                "--preset",
+                "protanopia",
+            ],
+            [
+                "--theme",
+                "dark",
+                "--preset",
+                "protanopia",
+                "--palette",
+                path,
+            ],
+        ] {
+            let (_, options) = parse(&[&["now"][..], &args].concat()).unwrap();
+            assert_eq!(options.style.colours, palette.colours, "{args:?}");
+        }
+        for args in [
+            ["--preset", "protanopia", "--theme", "dark"],
+            ["--theme", "dark", "--preset", "protanopia"],
+        ] {
+            let (_, options) = parse(&[&["now"][..], &args].concat()).unwrap();
+            assert_eq!(
+                options.style.colours,
+                PalettePreset::Protanopia.colours(true),
+                "{args:?}"
+            );
+        }
+        let _ = std::fs::remove_file(path);
+    }
+}
@@ -0,0 +1,245 @@
+//! Reading glyph strokes back into numbers.
+
+use crate::{
+    glyph::{Quadrant, Segment, StrokeKind},
+    CistercianNumeral,
+};
+use core::fmt;
+
+/// Set of the strokes, other than the stem, present in one quadrant.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Hash, serde::Deserialize, serde::Serialize,
+)]
+pub struct QuadrantStrokes(u8);
+
+impl QuadrantStrokes {
+    /// Stroke kinds which may appear in a quadrant, in drawing order.
+    pub const KINDS: [StrokeKind; 5] = [
+        StrokeKind::One,
+        StrokeKind::Two,
+        StrokeKind::Three,
+        StrokeKind::Four,
+        StrokeKind::Six,
+    ];
+
+    /// Strokes which draw `digit`. Digits above 9 give an empty set.
+    pub fn for_digit(digit: u8) -> Self {
+        StrokeKind::for_digit(digit).iter().copied().collect()
+    }
+
+    const fn bit(kind: StrokeKind) -> u8 {
+        match kind {
+            StrokeKind::Stem | StrokeKind::Bracket => 0,
+            StrokeKind::One => 1,
+            StrokeKind::Two => 1 << 1,
+            StrokeKind::Three => 1 << 2,
+            StrokeKind::Four => 1 << 3,
+            StrokeKind::Six => 1 << 4,
+        }
+    }
+
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub const fn contains(self, kind: StrokeKind) -> bool {
+        let bit = Self::bit(kind);
+        bit != 0 && self.0 & bit == bit
+    }
+
+    /// Adds `kind` to the set. The stem is shared by all quadrants, and brackets belong to whole
+    /// glyphs, so both are ignored.
+    pub fn insert(&mut self, kind: StrokeKind) {
+        self.0 |= Self::bit(kind);
+    }
+
+    pub fn remove(&mut self, kind: StrokeKind) {
+        self.0 &= !Self::bit(kind);
+    }
+
+    /// Adds `kind` if it is missing, and removes it otherwise.
+    pub fn toggle(&mut self, kind: StrokeKind) {
+        self.0 ^= Self::bit(kind);
+    }
+
+    /// Strokes in the set, in drawing order.
+    pub fn iter(self) -> impl Iterator<Item = StrokeKind> {
+        Self::KINDS
+            .into_iter()
+            .filter(move |&kind| self.contains(kind))
+    }
+
+    /// Digit drawn by these strokes, or `None` if they do not form one.
+    pub fn digit(self) -> Option<u8> {
+        (0..=9).find(|&digit| Self::for_digit(digit) == self)
+    }
+}
+
+impl FromIterator<StrokeKind> for QuadrantStrokes {
+    fn from_iter<T: IntoIterator<Item = StrokeKind>>(iter: T) -> Self {
+        let mut result = Self::default();
+        for kind in iter {
+            result.insert(kind);
+        }
+        result
+    }
+}
+
+/// Strokes, listed as `1 + 4`, or `none`.
+impl fmt::Display for QuadrantStrokes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "none");
+        }
+        for (index, kind) in self.iter().enumerate() {
+            if index > 0 {
+                write!(f, " + ")?;
+            }
+            write!(f, "{kind}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Reasons a set of strokes cannot be read as a number.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The segments have no stem to attach strokes to.
+    MissingStem,
+
+    /// The strokes in `quadrant` do not form any digit.
+    InvalidCombination {
+        quadrant: Quadrant,
+        strokes: QuadrantStrokes,
+
+        /// A pair of strokes which never appear together, when there is one. For example, 3 is
+        /// never combined with another stroke.
+        conflict: Option<(StrokeKind, StrokeKind)>,
+    },
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingStem => write!(f, "glyph has no stem"),
+            Self::InvalidCombination {
+                quadrant,
+                conflict: Some((first, second)),
+                ..
+            } => write!(
+                f,
+                "stroke {first} cannot be combined with stroke {second} in the {quadrant} quadrant"
+            ),
+            Self::InvalidCombination {
+                quadrant, strokes, ..
+            } => write!(
+                f,
+                "strokes {strokes} in the {quadrant} quadrant do not form a digit"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// Reads the number drawn by `strokes`, given in [`Quadrant::ALL`] order: units, tens, hundreds
+/// then thousands.
+pub fn decode(strokes: [QuadrantStrokes; 4]) -> Result<CistercianNumeral, DecodeError> {
+    let mut value: u16 = 0;
+    for (quadrant, strokes) in Quadrant::ALL.into_iter().zip(strokes) {
+        let digit = strokes
+            .digit()
+            .ok_or_else(|| DecodeError::InvalidCombination {
+                quadrant,
+                strokes,
+                conflict: conflict(strokes),
+            })?;
+        value += u16::from(digit) * quadrant.place_value();
+    }
+    Ok(CistercianNumeral::try_from(value).expect("Four decimal digits should fit in a numeral"))
+}
+
+/// Reads the number drawn by `segments`, such as those from [`crate::glyph::segments`] or a
+/// user-drawn glyph.
+pub fn decode_segments(segments: &[Segment]) -> Result<CistercianNumeral, DecodeError> {
+    let mut has_stem = false;
+    let mut strokes = [QuadrantStrokes::default(); 4];
+    for segment in segments {
+        match segment.quadrant {
+            None => has_stem |= segment.kind == StrokeKind::Stem,
+            Some(quadrant) => strokes[quadrant as usize].insert(segment.kind),
+        }
+    }
+    if !has_stem {
+        return Err(DecodeError::MissingStem);
+    }
+    decode(strokes)
+}
+
+/// First pair of strokes in `strokes` which no digit uses together.
+fn conflict(strokes: QuadrantStrokes) -> Option<(StrokeKind, StrokeKind)> {
+    let kinds: Vec<StrokeKind> = strokes.iter().collect();
+    kinds.iter().enumerate().find_map(|(index, &first)| {
+        kinds[index + 1..]
+            .iter()
+            .find(|&&second| {
+                !(1..=9).any(|digit| {
+                    let digit_strokes = QuadrantStrokes::for_digit(digit);
+                    digit_strokes.contains(first) && digit_strokes.contains(second)
+                })
+            })
+            .map(|&second| (first, second))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::glyph::{self, Orientation};
+
+    #[test]
+    fn decodes_every_glyph() {
+        for value in 0..=9999_u32 {
+            let numeral = CistercianNumeral::try_from(value).unwrap();
+            assert_eq!(
+                decode_segments(&glyph::segments(numeral, Orientation::Vertical)),
+                Ok(numeral),
+            );
+        }
+    }
+
+    #[test]
+    fn three_conflicts_with_one() {
+        let strokes = QuadrantStrokes::from_iter([StrokeKind::Three, StrokeKind::One]);
+        let empty = QuadrantStrokes::default();
+        assert_eq!(
+            decode([strokes, empty, empty, empty]),
+            Err(DecodeError::InvalidCombination {
+                quadrant: Quadrant::Units,
+                strokes,
+                conflict: Some((StrokeKind::One, StrokeKind::Three)),
+            }),
+        );
+    }
+
+    #[test]
+    fn one_and_two_have_no_conflict() {
+        // 1 and 2 appear together in 9, so neither stroke is to blame for the missing 6.
+        let strokes = QuadrantStrokes::from_iter([StrokeKind::One, StrokeKind::Two]);
+        let empty = QuadrantStrokes::default();
+        assert_eq!(
+            decode([empty, strokes, empty, empty]),
+            Err(DecodeError::InvalidCombination {
+                quadrant: Quadrant::Tens,
+                strokes,
+                conflict: None,
+            }),
+        );
+    }
+
+    #[test]
+    fn strokes_need_a_stem() {
+        let segments = [Segment::stroke(Quadrant::Units, StrokeKind::One)];
+        assert_eq!(decode_segments(&segments), Err(DecodeError::MissingStem));
+    }
+}
@@ -0,0 +1,499 @@
+//! Backend-agnostic stroke geometry for Cistercian glyphs.
+//!
+//! Segments are in glyph units, independent of any renderer: the origin is the centre of the
+//! glyph, `y` grows downwards and, at scale 1.0, one unit is one pixel.
+
+use crate::{CistercianNumeral, CompoundNumeral};
+use chrono::{NaiveTime, Timelike};
+use core::fmt;
+
+/// Width and height of the square cell a glyph is drawn in.
+pub const GLYPH_SIZE: f32 = 34.0;
+
+/// Distance from the glyph centre to either end of the stem.
+pub const STEM_HALF_LENGTH: f32 = GLYPH_SIZE / 2.0 - 1.0;
+
+/// Length of the strokes which branch off the stem.
+pub const ARM_LENGTH: f32 = 10.0;
+
+/// Gap, in pixels, between neighbouring glyphs in exported multi-glyph images.
+pub(crate) const GLYPH_GAP: f32 = 8.0;
+
+/// Numerals per row in exported reference charts.
+pub(crate) const CHART_COLUMNS: usize = 10;
+
+/// Stroke width, in pixels, used when drawing a glyph at `scale`.
+pub fn stroke_width(scale: f32) -> f32 {
+    if scale < 2.0 {
+        2.0
+    } else {
+        scale * 1.0
+    }
+}
+
+/// Point in glyph units.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct GlyphPoint {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl GlyphPoint {
+    pub const fn new(x: f32, y: f32) -> Self {
+        Self { x, y }
+    }
+}
+
+/// Which way the stem of a glyph runs.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Hash, serde::Deserialize, serde::Serialize,
+)]
+pub enum Orientation {
+    /// Upright stem, the usual form.
+    #[default]
+    Vertical,
+
+    /// Stem across the glyph, found in some medieval manuscripts: the vertical form turned a
+    /// quarter turn anticlockwise, so the units sit at the top left.
+    Horizontal,
+}
+
+impl Orientation {
+    pub const ALL: [Self; 2] = [Self::Vertical, Self::Horizontal];
+
+    /// `point`, given for the vertical form, moved to where it sits in this orientation.
+    pub fn orient(self, point: GlyphPoint) -> GlyphPoint {
+        match self {
+            Self::Vertical => point,
+            Self::Horizontal => GlyphPoint::new(point.y, -point.x),
+        }
+    }
+
+    /// `point`, as drawn in this orientation, moved back to where it sits in the vertical form.
+    /// The inverse of [`Self::orient`].
+    pub fn unorient(self, point: GlyphPoint) -> GlyphPoint {
+        match self {
+            Self::Vertical => point,
+            Self::Horizontal => GlyphPoint::new(-point.y, point.x),
+        }
+    }
+}
+
+/// Quarter of the glyph which shows one decimal digit. Positions are for the vertical form.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Quadrant {
+    /// Top right.
+    Units,
+
+    /// Top left.
+    Tens,
+
+    /// Bottom right.
+    Hundreds,
+
+    /// Bottom left.
+    Thousands,
+}
+
+impl Quadrant {
+    /// Quadrants in digit order, from units to thousands.
+    pub const ALL: [Self; 4] = [Self::Units, Self::Tens, Self::Hundreds, Self::Thousands];
+
+    /// Digit of `numeral` shown in this quadrant.
+    pub const fn digit(self, numeral: CistercianNumeral) -> u8 {
+        match self {
+            Self::Units => numeral.units(),
+            Self::Tens => numeral.tens(),
+            Self::Hundreds => numeral.hundreds(),
+            Self::Thousands => numeral.thousands(),
+        }
+    }
+
+    /// Place value of the digit shown in this quadrant.
+    pub const fn place_value(self) -> u16 {
+        match self {
+            Self::Units => 1,
+            Self::Tens => 10,
+            Self::Hundreds => 100,
+            Self::Thousands => 1_000,
+        }
+    }
+
+    /// Horizontal and vertical signs which mirror the units quadrant into this one.
+    const fn mirror(self) -> (f32, f32) {
+        match self {
+            Self::Units => (1.0, -1.0),
+            Self::Tens => (-1.0, -1.0),
+            Self::Hundreds => (1.0, 1.0),
+            Self::Thousands => (-1.0, 1.0),
+        }
+    }
+
+    /// Maps a point in the units quadrant's local frame into glyph units. `along` is measured
+    /// away from the stem, and `down` inwards from the outer end of the stem.
+    fn point(self, along: f32, down: f32) -> GlyphPoint {
+        let (x_sign, y_sign) = self.mirror();
+        GlyphPoint::new(x_sign * along, y_sign * (STEM_HALF_LENGTH - down))
+    }
+}
+
+/// Lower case name of the digit place, such as `units`.
+impl fmt::Display for Quadrant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Units => "units",
+            Self::Tens => "tens",
+            Self::Hundreds => "hundreds",
+            Self::Thousands => "thousands",
+        })
+    }
+}
+
+/// The kinds of stroke which make up a glyph. Every digit is drawn with one or more of the
+/// strokes for 1, 2, 3, 4 and 6, attached to the shared stem.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum StrokeKind {
+    /// Line through the centre, upright in the usual form, present in every glyph.
+    Stem,
+
+    /// Horizontal stroke at the end of the stem.
+    One,
+
+    /// Horizontal stroke one arm length in from the end of the stem.
+    Two,
+
+    /// Diagonal from the end of the stem, away and inwards.
+    Three,
+
+    /// Diagonal from one arm length in on the stem, out to the end.
+    Four,
+
+    /// Vertical stroke parallel to the stem, at the far end of the arms.
+    Six,
+
+    /// Bar under a glyph in a [`Grouping::Bracketed`] compound numeral, one for each power of
+    /// 10,000 the glyph is multiplied by.
+    Bracket,
+}
+
+impl StrokeKind {
+    /// Strokes, other than the stem, which together draw `digit`. Digits above 9 have no
+    /// strokes.
+    pub const fn for_digit(digit: u8) -> &'static [Self] {
+        match digit {
+            1 => &[Self::One],
+            2 => &[Self::Two],
+            3 => &[Self::Three],
+            4 => &[Self::Four],
+            5 => &[Self::One, Self::Four],
+            6 => &[Self::Six],
+            7 => &[Self::One, Self::Six],
+            8 => &[Self::Two, Self::Six],
+            9 => &[Self::One, Self::Two, Self::Six],
+            _ => &[],
+        }
+    }
+}
+
+/// The digit a stroke draws on its own, or `stem`.
+impl fmt::Display for StrokeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Stem => "stem",
+            Self::One => "1",
+            Self::Two => "2",
+            Self::Three => "3",
+            Self::Four => "4",
+            Self::Six => "6",
+            Self::Bracket => "bracket",
+        })
+    }
+}
+
+/// A single straight stroke of a glyph.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Segment {
+    /// Quadrant the stroke belongs to, or `None` for the stem.
+    pub quadrant: Option<Quadrant>,
+    pub kind: StrokeKind,
+    pub start: GlyphPoint,
+    pub end: GlyphPoint,
+}
+
+impl Segment {
+    /// The stem shared by every quadrant.
+    pub fn stem() -> Self {
+        Self {
+            quadrant: None,
+            kind: StrokeKind::Stem,
+            start: GlyphPoint::new(0.0, -STEM_HALF_LENGTH),
+            end: GlyphPoint::new(0.0, STEM_HALF_LENGTH),
+        }
+    }
+
+    /// Stroke `kind` drawn in `quadrant`.
+    pub fn stroke(quadrant: Quadrant, kind: StrokeKind) -> Self {
+        let ((start_along, start_down), (end_along, end_down)) = match kind {
+            StrokeKind::Stem | StrokeKind::Bracket => return Self::stem(),
+            StrokeKind::One => ((0.0, 0.0), (ARM_LENGTH, 0.0)),
+            StrokeKind::Two => ((0.0, ARM_LENGTH), (ARM_LENGTH, ARM_LENGTH)),
+            StrokeKind::Three => ((0.0, 0.0), (ARM_LENGTH, ARM_LENGTH)),
+            StrokeKind::Four => ((0.0, ARM_LENGTH), (ARM_LENGTH, 0.0)),
+            StrokeKind::Six => ((ARM_LENGTH, 0.0), (ARM_LENGTH, ARM_LENGTH)),
+        };
+        Self {
+            quadrant: Some(quadrant),
+            kind,
+            start: quadrant.point(start_along, start_down),
+            end: quadrant.point(end_along, end_down),
+        }
+    }
+
+    /// This segment, given for the vertical form, as drawn in `orientation`.
+    pub fn oriented(self, orientation: Orientation) -> Self {
+        Self {
+            start: orientation.orient(self.start),
+            end: orientation.orient(self.end),
+            ..self
+        }
+    }
+}
+
+/// Segments which draw `numeral` in `orientation`, starting with the stem, then each quadrant
+/// from units to thousands.
+pub fn segments(numeral: CistercianNumeral, orientation: Orientation) -> Vec<Segment> {
+    let mut result = vec![Segment::stem()];
+    for quadrant in Quadrant::ALL {
+        result.extend(
+            StrokeKind::for_digit(quadrant.digit(numeral))
+                .iter()
+                .map(|&kind| Segment::stroke(quadrant, kind)),
+        );
+    }
+    result
+        .into_iter()
+        .map(|segment| segment.oriented(orientation))
+        .collect()
+}
+
+/// Numerals a clock shows for `time`: one glyph for the hours and minutes, as `HHMM`, and one for
+/// the seconds. With `twelve_hour`, hours count 1–12 rather than 0–23, and nothing in the glyphs
+/// tells morning from afternoon, so pair them with a marker.
+pub fn time_numerals(time: NaiveTime, twelve_hour: bool) -> (CistercianNumeral, CistercianNumeral) {
+    let hour = match (twelve_hour, time.hour() % 12) {
+        (false, _) => time.hour(),
+        (true, 0) => 12,
+        (true, hour) => hour,
+    };
+    let hours_minutes = CistercianNumeral::try_from(hour * 100 + time.minute())
+        .expect("Hours and minutes should fit in a single numeral");
+    let seconds =
+        CistercianNumeral::try_from(time.second()).expect("Seconds should fit in a single numeral");
+    (hours_minutes, seconds)
+}
+
+/// Gap between neighbouring glyphs of a compound numeral.
+const COMPOUND_GAP: f32 = 6.0;
+
+/// Vertical distance between bracket bars, and from the first bar to the stem.
+const BRACKET_SPACING: f32 = 4.0;
+
+/// How a [`CompoundNumeral`] marks which power of 10,000 each of its glyphs stands for.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Hash, serde::Deserialize, serde::Serialize,
+)]
+pub enum Grouping {
+    /// One glyph per group of four digits, read left to right like Arabic digits, so
+    /// `10,000` is a 1 glyph followed by a bare stem.
+    #[default]
+    Positional,
+
+    /// Each glyph carries a bar underneath for every power of 10,000 it is multiplied by, so
+    /// groups of zeros can be left out and `10,000` is a single 1 glyph with one bar.
+    Bracketed,
+}
+
+/// Segments for a whole compound numeral, centred on the origin like a single glyph.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CompoundGlyph {
+    pub segments: Vec<Segment>,
+
+    /// Size of the box the glyphs are laid out in, in glyph units.
+    pub width: f32,
+    pub height: f32,
+}
+
+/// Lays out the glyphs for `number` side by side in `orientation`, marking groups with
+/// `grouping`. Bracket bars stay under the glyphs in either orientation.
+pub fn compound_glyph(
+    number: CompoundNumeral,
+    grouping: Grouping,
+    orientation: Orientation,
+) -> CompoundGlyph {
+    let mut groups = number.groups();
+    if grouping == Grouping::Bracketed && groups.len() > 1 {
+        groups.retain(|(numeral, _)| numeral.value() != 0);
+    }
+
+    let bracket_rows = match grouping {
+        Grouping::Positional => 0,
+        Grouping::Bracketed => groups.iter().map(|&(_, power)| power).max().unwrap_or(0),
+    };
+    let count = groups.len() as f32;
+    let width = count * GLYPH_SIZE + (count - 1.0) * COMPOUND_GAP;
+    let height = GLYPH_SIZE + bracket_rows as f32 * BRACKET_SPACING;
+    let centre_y = (GLYPH_SIZE - height) / 2.0;
+
+    let mut result = Vec::new();
+    for (index, (numeral, power)) in groups.into_iter().enumerate() {
+        let centre_x = -width / 2.0 + GLYPH_SIZE / 2.0 + index as f32 * (GLYPH_SIZE + COMPOUND_GAP);
+        let offset = |point: GlyphPoint| GlyphPoint::new(point.x + centre_x, point.y + centre_y);
+        result.extend(
+            segments(numeral, orientation)
+                .into_iter()
+                .map(|segment| Segment {
+                    start: offset(segment.start),
+                    end: offset(segment.end),
+                    ..segment
+                }),
+        );
+        if grouping == Grouping::Bracketed {
+            result.extend((1..=power).map(|row| {
+                let y = STEM_HALF_LENGTH + row as f32 * BRACKET_SPACING;
+                Segment {
+                    quadrant: None,
+                    kind: StrokeKind::Bracket,
+                    start: offset(GlyphPoint::new(-ARM_LENGTH, y)),
+                    end: offset(GlyphPoint::new(ARM_LENGTH, y)),
+                }
+            }));
+        }
+    }
+
+    CompoundGlyph {
+        segments: result,
+        width,
+        height,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Endpoints of `segment` in a fixed order, so strokes compare equal whichever end they
+    /// start from.
+    fn line(segment: &Segment) -> [(f32, f32); 2] {
+        let mut ends = [
+            (segment.start.x, segment.start.y),
+            (segment.end.x, segment.end.y),
+        ];
+        ends.sort_by(|first, second| first.partial_cmp(second).unwrap());
+        ends
+    }
+
+    fn lines(segments: &[Segment]) -> Vec<[(f32, f32); 2]> {
+        let mut lines: Vec<_> = segments.iter().map(line).collect();
+        lines.sort_by(|first, second| first.partial_cmp(second).unwrap());
+        lines
+    }
+
+    /// Lines the original per-quadrant painters drew for `digit`, stem included.
+    fn baseline_lines(quadrant: Quadrant, digit: u8) -> Vec<[(f32, f32); 2]> {
+        // Strokes for 1, 2, 3, 4 and 6 in the units quadrant, mirrored into the others.
+        let units = [
+            (
+                matches!(digit, 1 | 5 | 7 | 9),
+                [(0.0, -16.0), (10.0, -16.0)],
+            ),
+            (matches!(digit, 2 | 8 | 9), [(0.0, -6.0), (10.0, -6.0)]),
+            (digit == 3, [(0.0, -16.0), (10.0, -6.0)]),
+            (matches!(digit, 4 | 5), [(0.0, -6.0), (10.0, -16.0)]),
+            (digit > 5, [(10.0, -16.0), (10.0, -6.0)]),
+        ];
+        let (x_sign, y_sign) = match quadrant {
+            Quadrant::Units => (1.0, 1.0),
+            Quadrant::Tens => (-1.0, 1.0),
+            Quadrant::Hundreds => (1.0, -1.0),
+            Quadrant::Thousands => (-1.0, -1.0),
+        };
+        let mut lines: Vec<_> = units
+            .into_iter()
+            .filter(|&(drawn, _)| drawn)
+            .map(|(_, ends)| {
+                let mut ends = ends.map(|(x, y)| (x_sign * x, y_sign * y));
+                ends.sort_by(|first, second| first.partial_cmp(second).unwrap());
+                ends
+            })
+            .collect();
+        lines.push([(0.0, -16.0), (0.0, 16.0)]);
+        lines.sort_by(|first, second| first.partial_cmp(second).unwrap());
+        lines
+    }
+
+    #[test]
+    fn segments_match_baseline_painters() {
+        for quadrant in Quadrant::ALL {
+            for digit in 0..=9 {
+                let numeral = CistercianNumeral::try_from(
+                    u32::from(digit) * u32::from(quadrant.place_value()),
+                )
+                .unwrap();
+                assert_eq!(
+                    lines(&segments(numeral, Orientation::Vertical)),
+                    baseline_lines(quadrant, digit),
+                    "{digit} in the {quadrant} quadrant",
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn units_three_runs_from_stem_top() {
+        let numeral = CistercianNumeral::try_from(3_u32).unwrap();
+        let three = segments(numeral, Orientation::Vertical)[1];
+        assert_eq!(three.kind, StrokeKind::Three);
+        assert_eq!(three.start, GlyphPoint::new(0.0, -16.0));
+        assert_eq!(three.end, GlyphPoint::new(10.0, -6.0));
+    }
+
+    #[test]
+    fn horizontal_turns_units_to_top_left() {
+        let numeral = CistercianNumeral::try_from(1_u32).unwrap();
+        assert_eq!(
+            lines(&segments(numeral, Orientation::Horizontal)),
+            lines(&[
+                Segment {
+                    quadrant: None,
+                    kind: StrokeKind::Stem,
+                    start: GlyphPoint::new(-16.0, 0.0),
+                    end: GlyphPoint::new(16.0, 0.0),
+                },
+                Segment {
+                    quadrant: Some(Quadrant::Units),
+                    kind: StrokeKind::One,
+                    start: GlyphPoint::new(-16.0, 0.0),
+                    end: GlyphPoint::new(-16.0, -10.0),
+                },
+            ]),
+        );
+    }
+
+    #[test]
+    fn twelve_hour_time_counts_from_one() {
+        let numeral = |value: u32| CistercianNumeral::try_from(value).unwrap();
+        let time = NaiveTime::from_hms_opt(0, 5, 9).unwrap();
+        assert_eq!(time_numerals(time, false), (numeral(5), numeral(9)));
+        assert_eq!(time_numerals(time, true), (numeral(1_205), numeral(9)));
+        let time = NaiveTime::from_hms_opt(23, 59, 0).unwrap();
+        assert_eq!(time_numerals(time, true), (numeral(1_159), numeral(0)));
+    }
+
+    #[test]
+    fn unorient_reverses_orient() {
+        let point = GlyphPoint::new(10.0, -6.0);
+        for orientation in Orientation::ALL {
+            assert_eq!(orientation.unorient(orientation.orient(point)), point);
+        }
+    }
+}
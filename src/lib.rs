@@ -1,4 +1,20 @@
 #![warn(clippy::all, rust_2018_idioms)]
 
+#[cfg(feature = "app")]
 mod app;
+pub mod decode;
+pub mod glyph;
+mod numeral;
+pub mod palette;
+pub mod raster;
+pub mod style;
+pub mod svg;
+#[cfg(feature = "widget")]
+pub mod theme;
+pub mod transition;
+#[cfg(feature = "widget")]
+pub mod widget;
+
+#[cfg(feature = "app")]
 pub use app::CistercianClockApp;
+pub use numeral::{CistercianNumeral, CompoundNumeral, NumeralError};
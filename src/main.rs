@@ -22,7 +22,7 @@ fn main() -> eframe::Result<()> {
     let native_options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
             .with_inner_size([750.0, 600.0])
-            .with_min_inner_size([750.0, 600.0])
+            .with_min_inner_size([320.0, 240.0])
             .with_icon(icon_data),
         ..Default::default()
     };
@@ -0,0 +1,320 @@
+use core::{fmt, str::FromStr};
+
+/// A number in the range 0–9,999, which a single Cistercian glyph can show.
+///
+/// The four decimal digits map to the four quadrants of the glyph: units top right, tens top
+/// left, hundreds bottom right and thousands bottom left.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CistercianNumeral(u16);
+
+/// Reasons a value cannot become a [`CistercianNumeral`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NumeralError {
+    /// The value is above [`CistercianNumeral::MAX`].
+    OutOfRange,
+
+    /// The input string was empty, or only whitespace.
+    Empty,
+
+    /// The input string contains something other than decimal digits and thousands separators.
+    InvalidDigit,
+
+    /// The value is above [`u64::MAX`], so cannot be drawn even with compound glyphs.
+    Overflow,
+}
+
+impl fmt::Display for NumeralError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfRange => write!(f, "number must be between 0 and 9,999"),
+            Self::Empty => write!(f, "no number entered"),
+            Self::InvalidDigit => write!(f, "number may only contain the digits 0–9"),
+            Self::Overflow => write!(f, "number must be at most {:#}", CompoundNumeral(u64::MAX)),
+        }
+    }
+}
+
+impl std::error::Error for NumeralError {}
+
+impl CistercianNumeral {
+    /// Smallest value a glyph can show: a bare stem.
+    pub const MIN: Self = Self(0);
+
+    /// Largest value a glyph can show, with a 9 in every quadrant.
+    pub const MAX: Self = Self(9_999);
+
+    /// Numeric value of the numeral.
+    pub const fn value(self) -> u16 {
+        self.0
+    }
+
+    /// Units digit, drawn in the top-right quadrant.
+    pub const fn units(self) -> u8 {
+        (self.0 % 10) as u8
+    }
+
+    /// Tens digit, drawn in the top-left quadrant.
+    pub const fn tens(self) -> u8 {
+        ((self.0 / 10) % 10) as u8
+    }
+
+    /// Hundreds digit, drawn in the bottom-right quadrant.
+    pub const fn hundreds(self) -> u8 {
+        ((self.0 / 100) % 10) as u8
+    }
+
+    /// Thousands digit, drawn in the bottom-left quadrant.
+    pub const fn thousands(self) -> u8 {
+        (self.0 / 1_000) as u8
+    }
+
+    /// All four digits, ordered units, tens, hundreds, thousands.
+    pub const fn digits(self) -> [u8; 4] {
+        [self.units(), self.tens(), self.hundreds(), self.thousands()]
+    }
+}
+
+impl TryFrom<u32> for CistercianNumeral {
+    type Error = NumeralError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        if value > u32::from(Self::MAX.0) {
+            return Err(NumeralError::OutOfRange);
+        }
+        Ok(Self(value as u16))
+    }
+}
+
+impl TryFrom<u16> for CistercianNumeral {
+    type Error = NumeralError;
+
+    fn try_from(value: u16) -> Result<Self, Self::Error> {
+        Self::try_from(u32::from(value))
+    }
+}
+
+impl From<u8> for CistercianNumeral {
+    fn from(value: u8) -> Self {
+        Self(u16::from(value))
+    }
+}
+
+impl From<CistercianNumeral> for u16 {
+    fn from(value: CistercianNumeral) -> Self {
+        value.0
+    }
+}
+
+impl From<CistercianNumeral> for u32 {
+    fn from(value: CistercianNumeral) -> Self {
+        u32::from(value.0)
+    }
+}
+
+/// Formats the Arabic value. The alternate form, `{:#}`, adds a thousands separator, as in
+/// `1,984`.
+impl fmt::Display for CistercianNumeral {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            1_000.. if f.alternate() => write!(f, "{},{:003}", self.0 / 1_000, self.0 % 1_000),
+            value => fmt::Display::fmt(&value, f),
+        }
+    }
+}
+
+/// Parses a decimal number, optionally with a `,` thousands separator, so both `1984` and
+/// `1,984` are accepted.
+impl FromStr for CistercianNumeral {
+    type Err = NumeralError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match parse_grouped(s) {
+            Ok(value) => Self::try_from(value),
+            Err(NumeralError::Overflow) => Err(NumeralError::OutOfRange),
+            Err(error) => Err(error),
+        }
+    }
+}
+
+/// Any number up to [`u64::MAX`], drawn as a sequence of Cistercian glyphs, one for each group of
+/// four decimal digits.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CompoundNumeral(u64);
+
+impl CompoundNumeral {
+    pub const fn new(value: u64) -> Self {
+        Self(value)
+    }
+
+    pub const fn value(self) -> u64 {
+        self.0
+    }
+
+    /// Groups of four digits, most significant first, paired with the power of 10,000 each
+    /// group is multiplied by. There is always at least one group, so zero is a single bare
+    /// stem.
+    pub fn groups(self) -> Vec<(CistercianNumeral, u32)> {
+        let mut result = Vec::new();
+        let mut remaining = self.0;
+        let mut power = 0;
+        loop {
+            result.push((Self::group_numeral(remaining % 10_000), power));
+            remaining /= 10_000;
+            power += 1;
+            if remaining == 0 {
+                break;
+            }
+        }
+        result.reverse();
+        result
+    }
+
+    fn group_numeral(group: u64) -> CistercianNumeral {
+        CistercianNumeral::try_from(group).expect("Group of four digits should fit in a numeral")
+    }
+}
+
+impl From<u64> for CompoundNumeral {
+    fn from(value: u64) -> Self {
+        Self(value)
+    }
+}
+
+impl From<CistercianNumeral> for CompoundNumeral {
+    fn from(value: CistercianNumeral) -> Self {
+        Self(u64::from(value.0))
+    }
+}
+
+impl TryFrom<u64> for CistercianNumeral {
+    type Error = NumeralError;
+
+    fn try_from(value: u64) -> Result<Self, Self::Error> {
+        u32::try_from(value)
+            .map_err(|_| NumeralError::OutOfRange)
+            .and_then(Self::try_from)
+    }
+}
+
+/// Formats the Arabic value. The alternate form, `{:#}`, adds thousands separators.
+impl fmt::Display for CompoundNumeral {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !f.alternate() {
+            return fmt::Display::fmt(&self.0, f);
+        }
+
+        let digits = self.0.to_string();
+        for (index, digit) in digits.chars().enumerate() {
+            if index > 0 && (digits.len() - index) % 3 == 0 {
+                f.write_str(",")?;
+            }
+            write!(f, "{digit}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses a decimal number, optionally with `,` thousands separators.
+impl FromStr for CompoundNumeral {
+    type Err = NumeralError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_grouped(s).map(Self)
+    }
+}
+
+/// Parses a decimal number, which may use a `,` between every group of three digits.
+fn parse_grouped(s: &str) -> Result<u64, NumeralError> {
+    let s = s.trim();
+    if s.is_empty() {
+        return Err(NumeralError::Empty);
+    }
+
+    let mut groups = s.split(',');
+    let first = groups.next().unwrap_or_default();
+    if first.is_empty() || (first.len() > 3 && s.contains(',')) {
+        return Err(NumeralError::InvalidDigit);
+    }
+
+    let mut value: u64 = 0;
+    let mut overflowed = false;
+    for (index, group) in core::iter::once(first).chain(groups).enumerate() {
+        if index > 0 && group.len() != 3 {
+            return Err(NumeralError::InvalidDigit);
+        }
+        for character in group.chars() {
+            let digit = character.to_digit(10).ok_or(NumeralError::InvalidDigit)?;
+
+            // Keep checking the remaining characters after an overflow, so that invalid digits
+            // are still reported as such.
+            match value
+                .checked_mul(10)
+                .and_then(|value| value.checked_add(u64::from(digit)))
+            {
+                Some(next) => value = next,
+                None => overflowed = true,
+            }
+        }
+    }
+
+    if overflowed {
+        return Err(NumeralError::Overflow);
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_with_and_without_separators() {
+        assert_eq!(parse_grouped("1984"), Ok(1_984));
+        assert_eq!(parse_grouped(" 1,984 "), Ok(1_984));
+        assert_eq!(parse_grouped("12,345,678"), Ok(12_345_678));
+        assert_eq!(parse_grouped("0"), Ok(0));
+    }
+
+    #[test]
+    fn rejects_misplaced_separators() {
+        for input in ["1,9840", "9999,", ",123", "1,98", "1234,567", "1,,984"] {
+            assert_eq!(
+                parse_grouped(input),
+                Err(NumeralError::InvalidDigit),
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_empty_input() {
+        assert_eq!(parse_grouped(""), Err(NumeralError::Empty));
+        assert_eq!(parse_grouped("  "), Err(NumeralError::Empty));
+    }
+
+    #[test]
+    fn overflow_is_not_an_invalid_digit() {
+        assert_eq!(parse_grouped("18,446,744,073,709,551,615"), Ok(u64::MAX));
+        assert_eq!(
+            parse_grouped("18446744073709551616"),
+            Err(NumeralError::Overflow)
+        );
+        assert_eq!(
+            parse_grouped("18446744073709551616x"),
+            Err(NumeralError::InvalidDigit)
+        );
+    }
+
+    #[test]
+    fn single_glyph_limits() {
+        assert_eq!("9,999".parse(), Ok(CistercianNumeral::MAX));
+        assert_eq!(
+            "10,000".parse::<CistercianNumeral>(),
+            Err(NumeralError::OutOfRange)
+        );
+        assert_eq!(
+            "18446744073709551616".parse::<CistercianNumeral>(),
+            Err(NumeralError::OutOfRange)
+        );
+    }
+}
@@ -0,0 +1,356 @@
+//! Stroke colours for Cistercian glyphs.
+
+use crate::glyph::StrokeKind;
+use core::fmt;
+use ecolor::Color32;
+
+/// Colour for each kind of stroke, named by the digit the stroke draws on its own. Serialised
+/// with each colour as a `#rrggbbaa` hex string, so exported palettes are easy to edit by hand.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub struct Colours {
+    #[serde(with = "hex_colour")]
+    pub colour_0: Color32,
+    #[serde(with = "hex_colour")]
+    pub colour_1: Color32,
+    #[serde(with = "hex_colour")]
+    pub colour_2: Color32,
+    #[serde(with = "hex_colour")]
+    pub colour_3: Color32,
+    #[serde(with = "hex_colour")]
+    pub colour_4: Color32,
+    #[serde(with = "hex_colour")]
+    pub colour_6: Color32,
+}
+
+impl Colours {
+    /// Stroke kinds with a colour of their own, paired with a label for each. The bracket bars
+    /// share the stem colour.
+    pub const KINDS: [(StrokeKind, &'static str); 6] = [
+        (StrokeKind::Stem, "Stem"),
+        (StrokeKind::One, "1"),
+        (StrokeKind::Two, "2"),
+        (StrokeKind::Three, "3"),
+        (StrokeKind::Four, "4"),
+        (StrokeKind::Six, "6"),
+    ];
+
+    /// Mutable colour used to draw strokes of `kind`.
+    pub fn stroke_colour_mut(&mut self, kind: StrokeKind) -> &mut Color32 {
+        match kind {
+            StrokeKind::Stem | StrokeKind::Bracket => &mut self.colour_0,
+            StrokeKind::One => &mut self.colour_1,
+            StrokeKind::Two => &mut self.colour_2,
+            StrokeKind::Three => &mut self.colour_3,
+            StrokeKind::Four => &mut self.colour_4,
+            StrokeKind::Six => &mut self.colour_6,
+        }
+    }
+
+    /// Lowest contrast ratio of any stroke colour against `background`.
+    pub fn min_contrast(&self, background: Color32) -> f32 {
+        Self::KINDS
+            .iter()
+            .map(|&(kind, _)| contrast_ratio(self.stroke_colour(kind), background))
+            .fold(f32::INFINITY, f32::min)
+    }
+
+    /// Colour used to draw strokes of `kind`.
+    pub fn stroke_colour(&self, kind: StrokeKind) -> Color32 {
+        match kind {
+            StrokeKind::Stem | StrokeKind::Bracket => self.colour_0,
+            StrokeKind::One => self.colour_1,
+            StrokeKind::Two => self.colour_2,
+            StrokeKind::Three => self.colour_3,
+            StrokeKind::Four => self.colour_4,
+            StrokeKind::Six => self.colour_6,
+        }
+    }
+}
+
+/// Lowest contrast ratio, against the background, that WCAG asks of the parts of a graphic
+/// needed to understand it.
+pub const MIN_GRAPHIC_CONTRAST: f32 = 3.0;
+
+/// WCAG relative luminance of `colour`, from 0.0 for black to 1.0 for white, ignoring alpha.
+pub fn relative_luminance(colour: Color32) -> f32 {
+    let linear = |channel: u8| {
+        let channel = f32::from(channel) / 255.0;
+        if channel <= 0.040_45 {
+            channel / 12.92
+        } else {
+            ((channel + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    let [red, green, blue, _] = colour.to_srgba_unmultiplied();
+    0.2126 * linear(red) + 0.7152 * linear(green) + 0.0722 * linear(blue)
+}
+
+/// WCAG contrast ratio between two colours, from 1.0 for identical luminance up to 21.0 for
+/// black on white.
+pub fn contrast_ratio(first: Color32, second: Color32) -> f32 {
+    let (first, second) = (relative_luminance(first), relative_luminance(second));
+    (first.max(second) + 0.05) / (first.min(second) + 0.05)
+}
+
+/// `colour` with its alpha scaled by `opacity`, keeping the hue unchanged.
+pub fn with_opacity(colour: Color32, opacity: f32) -> Color32 {
+    let [red, green, blue, alpha] = colour.to_srgba_unmultiplied();
+    let alpha = (f32::from(alpha) * opacity.clamp(0.0, 1.0)).round() as u8;
+    Color32::from_rgba_unmultiplied(red, green, blue, alpha)
+}
+
+pub const DARK_CISTERCIAN_NUMERAL_COLOURS: Colours = Colours {
+    colour_0: Color32::from_gray(242),
+    colour_1: Color32::from_rgb(58, 134, 255),
+    colour_2: Color32::from_rgb(251, 86, 7),
+    colour_3: Color32::from_rgb(162, 106, 241),
+    colour_4: Color32::from_rgb(255, 0, 110),
+    colour_6: Color32::from_rgb(255, 190, 11),
+};
+
+pub const LIGHT_CISTERCIAN_NUMERAL_COLOURS: Colours = Colours {
+    colour_0: Color32::from_rgb(4, 3, 15),
+    colour_1: Color32::from_rgb(93, 93, 91),
+    colour_2: Color32::from_rgb(0, 122, 94),
+    colour_3: Color32::from_rgb(27, 42, 65),
+    colour_4: Color32::from_rgb(150, 2, 0),
+    colour_6: Color32::from_rgb(0, 122, 163),
+};
+
+/// Built-in palettes for readers who find the default hues hard to tell apart. Each has a
+/// variant for dark backgrounds and one for light backgrounds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, serde::Deserialize, serde::Serialize)]
+pub enum PalettePreset {
+    /// Red–green colour blindness with weak green cones. Tells strokes apart along the
+    /// blue–orange axis and by lightness.
+    Deuteranopia,
+
+    /// Red–green colour blindness with weak red cones, in which reds also look darker. Avoids
+    /// leaning on reds.
+    Protanopia,
+
+    /// Blue–yellow colour blindness. Tells strokes apart along the red–cyan axis.
+    Tritanopia,
+
+    /// A single colour with the most contrast against the background. Pair it with dash
+    /// patterns, so strokes differ by more than position.
+    HighContrast,
+}
+
+impl PalettePreset {
+    pub const ALL: [Self; 4] = [
+        Self::Deuteranopia,
+        Self::Protanopia,
+        Self::Tritanopia,
+        Self::HighContrast,
+    ];
+
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Deuteranopia => "Deuteranopia",
+            Self::Protanopia => "Protanopia",
+            Self::Tritanopia => "Tritanopia",
+            Self::HighContrast => "High contrast",
+        }
+    }
+
+    /// Variant for a dark background when `dark_mode` is set, otherwise for a light one.
+    pub const fn colours(self, dark_mode: bool) -> Colours {
+        match (self, dark_mode) {
+            (Self::Deuteranopia, true) => Colours {
+                colour_0: Color32::from_gray(242),
+                colour_1: Color32::from_rgb(86, 180, 233),
+                colour_2: Color32::from_rgb(230, 159, 0),
+                colour_3: Color32::from_rgb(240, 228, 66),
+                colour_4: Color32::from_rgb(204, 121, 167),
+                colour_6: Color32::from_rgb(61, 143, 209),
+            },
+            (Self::Deuteranopia, false) => Colours {
+                colour_0: Color32::from_rgb(4, 3, 15),
+                colour_1: Color32::from_rgb(0, 114, 178),
+                colour_2: Color32::from_rgb(161, 92, 0),
+                colour_3: Color32::from_rgb(107, 97, 0),
+                colour_4: Color32::from_rgb(164, 71, 124),
+                colour_6: Color32::from_rgb(31, 78, 121),
+            },
+            (Self::Protanopia, true) => Colours {
+                colour_0: Color32::from_gray(242),
+                colour_1: Color32::from_rgb(108, 182, 255),
+                colour_2: Color32::from_rgb(240, 228, 66),
+                colour_3: Color32::from_rgb(230, 159, 0),
+                colour_4: Color32::from_rgb(185, 164, 245),
+                colour_6: Color32::from_rgb(0, 166, 166),
+            },
+            (Self::Protanopia, false) => Colours {
+                colour_0: Color32::from_rgb(4, 3, 15),
+                colour_1: Color32::from_rgb(0, 94, 168),
+                colour_2: Color32::from_rgb(122, 106, 0),
+                colour_3: Color32::from_rgb(163, 84, 0),
+                colour_4: Color32::from_rgb(106, 79, 191),
+                colour_6: Color32::from_rgb(0, 112, 111),
+            },
+            (Self::Tritanopia, true) => Colours {
+                colour_0: Color32::from_gray(242),
+                colour_1: Color32::from_rgb(255, 107, 107),
+                colour_2: Color32::from_rgb(46, 196, 196),
+                colour_3: Color32::from_rgb(247, 161, 208),
+                colour_4: Color32::from_gray(201),
+                colour_6: Color32::from_rgb(217, 140, 255),
+            },
+            (Self::Tritanopia, false) => Colours {
+                colour_0: Color32::from_rgb(4, 3, 15),
+                colour_1: Color32::from_rgb(198, 40, 40),
+                colour_2: Color32::from_rgb(0, 112, 122),
+                colour_3: Color32::from_rgb(176, 48, 111),
+                colour_4: Color32::from_gray(90),
+                colour_6: Color32::from_rgb(123, 47, 179),
+            },
+            (Self::HighContrast, dark_mode) => {
+                let colour = if dark_mode {
+                    Color32::WHITE
+                } else {
+                    Color32::BLACK
+                };
+                Colours {
+                    colour_0: colour,
+                    colour_1: colour,
+                    colour_2: colour,
+                    colour_3: colour,
+                    colour_4: colour,
+                    colour_6: colour,
+                }
+            }
+        }
+    }
+}
+
+/// A user palette, saved under a name.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub struct Palette {
+    pub name: String,
+    pub colours: Colours,
+}
+
+/// Text formats palettes can be imported from and exported to.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum PaletteFormat {
+    #[default]
+    Json,
+    Toml,
+}
+
+impl PaletteFormat {
+    /// Format for a file with `extension`, if it is one of the supported formats.
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_ascii_lowercase().as_str() {
+            "json" => Some(Self::Json),
+            "toml" => Some(Self::Toml),
+            _ => None,
+        }
+    }
+}
+
+/// Why an imported palette could not be read.
+#[derive(Debug)]
+pub enum PaletteError {
+    Json(serde_json::Error),
+    Toml(toml::de::Error),
+}
+
+impl fmt::Display for PaletteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Json(error) => write!(f, "invalid JSON palette: {error}"),
+            Self::Toml(error) => write!(f, "invalid TOML palette: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for PaletteError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Json(error) => Some(error),
+            Self::Toml(error) => Some(error),
+        }
+    }
+}
+
+impl Palette {
+    /// Palette written out in `format`.
+    pub fn export(&self, format: PaletteFormat) -> String {
+        match format {
+            PaletteFormat::Json => {
+                serde_json::to_string_pretty(self).expect("Palette should serialise to JSON")
+            }
+            PaletteFormat::Toml => toml::to_string(self).expect("Palette should serialise to TOML"),
+        }
+    }
+
+    /// Reads a palette written in `format`, such as one made by [`Palette::export`].
+    pub fn import(text: &str, format: PaletteFormat) -> Result<Self, PaletteError> {
+        match format {
+            PaletteFormat::Json => serde_json::from_str(text).map_err(PaletteError::Json),
+            PaletteFormat::Toml => toml::from_str(text).map_err(PaletteError::Toml),
+        }
+    }
+}
+
+/// Serialises a colour as a `#rrggbbaa` string, and reads any form [`Color32::from_hex`]
+/// accepts.
+mod hex_colour {
+    use ecolor::Color32;
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(colour: &Color32, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&colour.to_hex())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Color32, D::Error> {
+        let hex = String::deserialize(deserializer)?;
+        Color32::from_hex(&hex).map_err(|_| D::Error::custom(format!("invalid colour `{hex}`")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn palette() -> Palette {
+        Palette {
+            name: "Translucent".to_owned(),
+            colours: Colours {
+                colour_0: Color32::from_rgba_unmultiplied(0x12, 0x34, 0x56, 0x80),
+                ..DARK_CISTERCIAN_NUMERAL_COLOURS
+            },
+        }
+    }
+
+    #[test]
+    fn round_trips_through_json() {
+        let text = palette().export(PaletteFormat::Json);
+        assert_eq!(
+            Palette::import(&text, PaletteFormat::Json).unwrap(),
+            palette()
+        );
+    }
+
+    #[test]
+    fn round_trips_through_toml() {
+        let text = palette().export(PaletteFormat::Toml);
+        assert_eq!(
+            Palette::import(&text, PaletteFormat::Toml).unwrap(),
+            palette()
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_colours() {
+        let text = palette()
+            .export(PaletteFormat::Json)
+            .replace(&palette().colours.colour_0.to_hex(), "#12345g");
+        assert!(matches!(
+            Palette::import(&text, PaletteFormat::Json),
+            Err(PaletteError::Json(_))
+        ));
+    }
+}
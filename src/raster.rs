@@ -0,0 +1,436 @@
+//! Software rasteriser which draws glyph strokes into an [`RgbaImage`], without a window or GPU.
+
+use crate::{
+    glyph::{self, CompoundGlyph, GlyphPoint, Grouping, Segment, CHART_COLUMNS, GLYPH_GAP},
+    palette,
+    style::{GlyphStyle, OutlineShape, StrokeOutline},
+    transition::{self, AnimatedSegment, TransitionStyle},
+    CistercianNumeral, CompoundNumeral,
+};
+use chrono::NaiveTime;
+use core::ops::Range;
+use ecolor::Color32;
+use image::{ImageFormat, ImageResult, Rgba, RgbaImage};
+use std::path::Path;
+
+/// Fill for pixels not covered by a stroke.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Background {
+    #[default]
+    Transparent,
+    Solid(Color32),
+}
+
+/// Image `width` × `height` pixels with a single glyph for `numeral`, scaled to fit and centred.
+pub fn numeral_image(
+    numeral: CistercianNumeral,
+    width: u32,
+    height: u32,
+    style: &GlyphStyle,
+    background: Background,
+) -> RgbaImage {
+    let mut image = blank(width, height, background);
+    let scale = style.fit_scale((width as f32, height as f32), (1.0, 1.0), (0.0, 0.0));
+    draw_glyph(
+        &mut image,
+        numeral,
+        (width as f32 / 2.0, height as f32 / 2.0),
+        scale,
+        style,
+    );
+    image
+}
+
+/// Image with the glyphs for a number of any size, grouped by `grouping` and drawn at `scale`.
+pub fn compound_image(
+    number: CompoundNumeral,
+    grouping: Grouping,
+    scale: f32,
+    style: &GlyphStyle,
+    background: Background,
+) -> RgbaImage {
+    let segments = glyph::compound_glyph(number, grouping, style.orientation).segments;
+    let (width, height) = compound_size(number, grouping, scale, style);
+    let mut image = blank(width, height, background);
+    draw_segments(
+        &mut image,
+        &segments,
+        (width as f32 / 2.0, height as f32 / 2.0),
+        scale,
+        style,
+    );
+    image
+}
+
+/// Width and height, in pixels, of the image from [`compound_image`].
+pub fn compound_size(
+    number: CompoundNumeral,
+    grouping: Grouping,
+    scale: f32,
+    style: &GlyphStyle,
+) -> (u32, u32) {
+    let CompoundGlyph { width, height, .. } =
+        glyph::compound_glyph(number, grouping, style.orientation);
+    let margin = 2.0 * style.margin(scale);
+    pixels(scale * (width + margin), scale * (height + margin))
+}
+
+/// Image showing `time` as the clock does, with an hours-minutes glyph followed by a seconds
+/// glyph, each drawn at `scale`.
+pub fn time_image(
+    time: NaiveTime,
+    scale: f32,
+    style: &GlyphStyle,
+    background: Background,
+) -> RgbaImage {
+    let (hours_minutes, seconds) = glyph::time_numerals(time, false);
+
+    let size = scale * style.cell_size(scale);
+    let (width, height) = time_size(scale, style);
+    let mut image = blank(width, height, background);
+    draw_glyph(
+        &mut image,
+        hours_minutes,
+        (size / 2.0, size / 2.0),
+        scale,
+        style,
+    );
+    draw_glyph(
+        &mut image,
+        seconds,
+        (size + GLYPH_GAP + size / 2.0, size / 2.0),
+        scale,
+        style,
+    );
+    image
+}
+
+/// Width and height, in pixels, of the image from [`time_image`].
+pub fn time_size(scale: f32, style: &GlyphStyle) -> (u32, u32) {
+    let size = scale * style.cell_size(scale);
+    pixels(2.0 * size + GLYPH_GAP, size)
+}
+
+/// Reference chart of every number in `numbers`, ten to a row, drawn at `scale`. Unlike the SVG
+/// chart, glyphs are not captioned. Numbers which do not fit in a single glyph are skipped.
+pub fn chart_image(
+    numbers: Range<u32>,
+    scale: f32,
+    style: &GlyphStyle,
+    background: Background,
+) -> RgbaImage {
+    let size = scale * style.cell_size(scale);
+    let cell = size + GLYPH_GAP;
+
+    let (width, height) = chart_size(&numbers, scale, style);
+    let numerals: Vec<CistercianNumeral> = numbers
+        .filter_map(|number| CistercianNumeral::try_from(number).ok())
+        .collect();
+
+    let mut image = blank(width, height, background);
+    for (index, numeral) in numerals.into_iter().enumerate() {
+        let centre = (
+            (index % CHART_COLUMNS) as f32 * cell + size / 2.0,
+            (index / CHART_COLUMNS) as f32 * cell + size / 2.0,
+        );
+        draw_glyph(&mut image, numeral, centre, scale, style);
+    }
+    image
+}
+
+/// Width and height, in pixels, of the image from [`chart_image`].
+pub fn chart_size(numbers: &Range<u32>, scale: f32, style: &GlyphStyle) -> (u32, u32) {
+    let cell = scale * style.cell_size(scale) + GLYPH_GAP;
+    let limit = u32::from(CistercianNumeral::MAX) + 1;
+    let count = numbers
+        .end
+        .min(limit)
+        .saturating_sub(numbers.start.min(limit)) as usize;
+    let rows = count.div_ceil(CHART_COLUMNS);
+    let columns = count.min(CHART_COLUMNS);
+    pixels(columns as f32 * cell, rows as f32 * cell)
+}
+
+/// One image per frame of the animated change from `from` to `to`, each a single glyph drawn at
+/// `scale`. See [`transition::transition_frames`].
+pub fn transition_images(
+    from: CistercianNumeral,
+    to: CistercianNumeral,
+    transition: TransitionStyle,
+    frames: usize,
+    scale: f32,
+    style: &GlyphStyle,
+    background: Background,
+) -> Vec<RgbaImage> {
+    let size = scale * style.cell_size(scale);
+    let (width, height) = glyph_size(scale, style);
+    transition::transition_frames(from, to, transition, style.orientation, frames)
+        .into_iter()
+        .map(|segments| {
+            let mut image = blank(width, height, background);
+            draw_animated_segments(
+                &mut image,
+                &segments,
+                (size / 2.0, size / 2.0),
+                scale,
+                style,
+            );
+            image
+        })
+        .collect()
+}
+
+/// Width and height, in pixels, of an image holding a single glyph at `scale`, such as each of
+/// the [`transition_images`].
+pub fn glyph_size(scale: f32, style: &GlyphStyle) -> (u32, u32) {
+    let size = scale * style.cell_size(scale);
+    pixels(size, size)
+}
+
+/// Saves `image` to `path` as a PNG, whatever the file extension.
+pub fn save_png(image: &RgbaImage, path: impl AsRef<Path>) -> ImageResult<()> {
+    image.save_with_format(path, ImageFormat::Png)
+}
+
+/// Draws the strokes for `numeral` with the glyph centre at `centre`, in pixels.
+pub fn draw_glyph(
+    image: &mut RgbaImage,
+    numeral: CistercianNumeral,
+    centre: (f32, f32),
+    scale: f32,
+    style: &GlyphStyle,
+) {
+    draw_segments(
+        image,
+        &glyph::segments(numeral, style.orientation),
+        centre,
+        scale,
+        style,
+    );
+}
+
+/// Draws `segments`, in glyph units, with the glyph origin at `centre`, in pixels.
+pub fn draw_segments(
+    image: &mut RgbaImage,
+    segments: &[Segment],
+    centre: (f32, f32),
+    scale: f32,
+    style: &GlyphStyle,
+) {
+    for outline in style.outlines(segments, scale) {
+        let colour = style.colours.stroke_colour(outline.kind);
+        fill_shapes(image, &outline, centre, scale, colour);
+    }
+}
+
+/// Draws `segments` part way through a transition, with the glyph origin at `centre`, in pixels.
+pub fn draw_animated_segments(
+    image: &mut RgbaImage,
+    segments: &[AnimatedSegment],
+    centre: (f32, f32),
+    scale: f32,
+    style: &GlyphStyle,
+) {
+    let plain: Vec<Segment> = segments.iter().map(|animated| animated.segment).collect();
+    for (outline, animated) in style.outlines(&plain, scale).iter().zip(segments) {
+        let colour =
+            palette::with_opacity(style.colours.stroke_colour(outline.kind), animated.opacity);
+        fill_shapes(image, outline, centre, scale, colour);
+    }
+}
+
+/// Whole pixels covering `width` × `height`.
+fn pixels(width: f32, height: f32) -> (u32, u32) {
+    (width.ceil() as u32, height.ceil() as u32)
+}
+
+fn blank(width: u32, height: u32, background: Background) -> RgbaImage {
+    let fill = match background {
+        Background::Transparent => Rgba([0, 0, 0, 0]),
+        Background::Solid(colour) => Rgba(colour.to_srgba_unmultiplied()),
+    };
+    RgbaImage::from_pixel(width, height, fill)
+}
+
+/// Fills the union of the shapes of `outline`, in glyph units, with the glyph origin at `centre`. Each pixel
+/// is covered in proportion to how far its centre lies inside the nearest shape edge, which
+/// antialiases the edges, and shapes of one stroke overlap without darkening.
+fn fill_shapes(
+    image: &mut RgbaImage,
+    outline: &StrokeOutline,
+    (centre_x, centre_y): (f32, f32),
+    scale: f32,
+    colour: Color32,
+) {
+    let to_pixels = |point: &GlyphPoint| (centre_x + scale * point.x, centre_y + scale * point.y);
+    let shapes: Vec<PixelShape> = outline
+        .shapes()
+        .map(|shape| match shape {
+            OutlineShape::Polygon(points) => {
+                PixelShape::Polygon(points.iter().map(to_pixels).collect())
+            }
+            OutlineShape::Disc { centre, radius } => PixelShape::Disc {
+                centre: to_pixels(centre),
+                radius: scale * radius,
+            },
+        })
+        .collect();
+    let Some((min_x, min_y, max_x, max_y)) =
+        shapes
+            .iter()
+            .map(PixelShape::bounds)
+            .reduce(|first, second| {
+                (
+                    first.0.min(second.0),
+                    first.1.min(second.1),
+                    first.2.max(second.2),
+                    first.3.max(second.3),
+                )
+            })
+    else {
+        return;
+    };
+
+    let min_x = (min_x - 1.0).floor().max(0.0) as u32;
+    let min_y = (min_y - 1.0).floor().max(0.0) as u32;
+    let max_x = ((max_x + 1.0).ceil().max(0.0) as u32).min(image.width());
+    let max_y = ((max_y + 1.0).ceil().max(0.0) as u32).min(image.height());
+    for y in min_y..max_y {
+        for x in min_x..max_x {
+            let pixel = (x as f32 + 0.5, y as f32 + 0.5);
+            let coverage = shapes
+                .iter()
+                .map(|shape| (shape.depth(pixel) + 0.5).clamp(0.0, 1.0))
+                .fold(0.0, f32::max);
+            if coverage > 0.0 {
+                blend(image.get_pixel_mut(x, y), colour, coverage);
+            }
+        }
+    }
+}
+
+/// [`OutlineShape`] in pixels.
+enum PixelShape {
+    Polygon(Vec<(f32, f32)>),
+    Disc { centre: (f32, f32), radius: f32 },
+}
+
+impl PixelShape {
+    /// Smallest and largest x and y covered.
+    fn bounds(&self) -> (f32, f32, f32, f32) {
+        match self {
+            Self::Polygon(points) => points.iter().fold(
+                (
+                    f32::INFINITY,
+                    f32::INFINITY,
+                    f32::NEG_INFINITY,
+                    f32::NEG_INFINITY,
+                ),
+                |(min_x, min_y, max_x, max_y), &(x, y)| {
+                    (min_x.min(x), min_y.min(y), max_x.max(x), max_y.max(y))
+                },
+            ),
+            Self::Disc {
+                centre: (x, y),
+                radius,
+            } => (x - radius, y - radius, x + radius, y + radius),
+        }
+    }
+
+    /// Distance from `point` in to the nearest edge, negative outside the shape.
+    fn depth(&self, (x, y): (f32, f32)) -> f32 {
+        match self {
+            Self::Polygon(points) => points
+                .iter()
+                .zip(points.iter().cycle().skip(1))
+                .map(|(&(start_x, start_y), &(end_x, end_y))| {
+                    let (edge_x, edge_y) = (end_x - start_x, end_y - start_y);
+                    let length = edge_x.hypot(edge_y).max(f32::EPSILON);
+                    // Points run clockwise on screen, so the inside is to the right of each edge.
+                    (edge_x * (y - start_y) - edge_y * (x - start_x)) / length
+                })
+                .fold(f32::INFINITY, f32::min),
+            Self::Disc {
+                centre: (centre_x, centre_y),
+                radius,
+            } => radius - (x - centre_x).hypot(y - centre_y),
+        }
+    }
+}
+
+/// Source-over blend of `colour`, with its alpha reduced by `coverage`, onto `pixel`.
+fn blend(pixel: &mut Rgba<u8>, colour: Color32, coverage: f32) {
+    let [red, green, blue, alpha] = colour.to_srgba_unmultiplied();
+    let source_alpha = f32::from(alpha) / 255.0 * coverage;
+    let destination_alpha = f32::from(pixel[3]) / 255.0;
+    let out_alpha = source_alpha + destination_alpha * (1.0 - source_alpha);
+    if out_alpha <= 0.0 {
+        return;
+    }
+
+    let mix = |source: u8, destination: u8| {
+        let value = (f32::from(source) * source_alpha
+            + f32::from(destination) * destination_alpha * (1.0 - source_alpha))
+            / out_alpha;
+        value.round().clamp(0.0, 255.0) as u8
+    };
+    *pixel = Rgba([
+        mix(red, pixel[0]),
+        mix(green, pixel[1]),
+        mix(blue, pixel[2]),
+        (out_alpha * 255.0).round() as u8,
+    ]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BACKGROUND: Color32 = Color32::from_rgb(0x20, 0x40, 0x60);
+
+    fn image(background: Background) -> RgbaImage {
+        numeral_image(
+            CistercianNumeral::try_from(1_984_u32).unwrap(),
+            65,
+            65,
+            &GlyphStyle::default(),
+            background,
+        )
+    }
+
+    #[test]
+    fn draws_stem_in_its_colour() {
+        let stem = GlyphStyle::default().colours.colour_0;
+        let image = image(Background::Solid(BACKGROUND));
+        assert_eq!(*image.get_pixel(32, 32), Rgba(stem.to_srgba_unmultiplied()));
+    }
+
+    #[test]
+    fn leaves_corners_as_background() {
+        let image = image(Background::Solid(BACKGROUND));
+        let background = Rgba(BACKGROUND.to_srgba_unmultiplied());
+        for (x, y) in [(0, 0), (64, 0), (0, 64), (64, 64)] {
+            assert_eq!(*image.get_pixel(x, y), background, "({x}, {y})");
+        }
+    }
+
+    #[test]
+    fn antialiases_edges() {
+        let image = image(Background::Transparent);
+        assert!(image.pixels().any(|pixel| pixel[3] == u8::MAX));
+        assert!(image
+            .pixels()
+            .any(|pixel| pixel[3] > 0 && pixel[3] < u8::MAX));
+        assert_eq!(image.get_pixel(0, 0)[3], 0);
+    }
+
+    #[test]
+    fn sizes_match_images() {
+        let style = GlyphStyle::default();
+        let image = chart_image(0..15, 1.5, &style, Background::Transparent);
+        assert_eq!(image.dimensions(), chart_size(&(0..15), 1.5, &style));
+        let time = NaiveTime::from_hms_opt(1, 2, 3).unwrap();
+        let image = time_image(time, 1.5, &style, Background::Transparent);
+        assert_eq!(image.dimensions(), time_size(1.5, &style));
+    }
+}
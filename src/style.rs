@@ -0,0 +1,862 @@
+//! How glyph strokes are drawn, beyond their geometry.
+//!
+//! Every renderer fills the same outlines, worked out here from the segments, so caps, joins and
+//! the manuscript pen look the same in the app, in SVG and in PNG output.
+
+use crate::{
+    glyph::{self, GlyphPoint, Orientation, Segment, StrokeKind, GLYPH_SIZE, STEM_HALF_LENGTH},
+    palette::{Colours, LIGHT_CISTERCIAN_NUMERAL_COLOURS},
+};
+
+/// Everything a renderer needs, besides the segments, to draw a glyph.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GlyphStyle {
+    pub colours: Colours,
+
+    /// Draw each kind of stroke with its own dash pattern, so a glyph can be read without
+    /// telling its colours apart.
+    pub patterned: bool,
+
+    pub stroke: StrokeStyle,
+
+    /// Form of the glyphs renderers lay out from numerals. Segments passed in directly are
+    /// drawn as they are.
+    pub orientation: Orientation,
+}
+
+impl GlyphStyle {
+    /// Solid strokes in `colours`, with the default [`StrokeStyle`].
+    pub const fn new(colours: Colours) -> Self {
+        Self {
+            colours,
+            patterned: false,
+            stroke: StrokeStyle::DEFAULT,
+            orientation: Orientation::Vertical,
+        }
+    }
+
+    /// `segments` as they should be drawn: split into dashes when the style is patterned.
+    pub fn apply(&self, segments: &[Segment]) -> Vec<Segment> {
+        if self.patterned {
+            segments
+                .iter()
+                .flat_map(|&segment| dashes(segment))
+                .collect()
+        } else {
+            segments.to_vec()
+        }
+    }
+
+    /// Space, in glyph units, to leave on each side of the usual glyph cell of [`GLYPH_SIZE`]
+    /// for ink which reaches past it at `scale`.
+    pub fn margin(&self, scale: f32) -> f32 {
+        (STEM_HALF_LENGTH + self.stroke.overhang(scale) - GLYPH_SIZE / 2.0).max(0.0)
+    }
+
+    /// Side of the square cell, in glyph units, which holds every stroke of a glyph drawn at
+    /// `scale`, caps, joins and all.
+    pub fn cell_size(&self, scale: f32) -> f32 {
+        GLYPH_SIZE + 2.0 * self.margin(scale)
+    }
+
+    /// Largest scale at which `cells` glyph cells across and down, plus `extra_units` glyph units
+    /// across and down, fit in `available` pixels across and down.
+    pub fn fit_scale(
+        &self,
+        available: (f32, f32),
+        cells: (f32, f32),
+        extra_units: (f32, f32),
+    ) -> f32 {
+        let fit = |cell: f32| {
+            let across = available.0 / (cells.0 * cell + extra_units.0);
+            let down = available.1 / (cells.1 * cell + extra_units.1);
+            across.min(down)
+        };
+        // Below a scale of 2 the stroke keeps its width in pixels, so the cell grows as the scale
+        // shrinks. Search between nothing and the fit for the usual cell, which is never
+        // smaller, for the largest scale whose own cell fits.
+        let fits = |scale: f32| scale <= fit(self.cell_size(scale));
+        let (mut low, mut high) = (0.0, fit(GLYPH_SIZE));
+        if high <= 0.0 || fits(high) {
+            return high;
+        }
+        for _ in 0..32 {
+            let middle = (low + high) / 2.0;
+            if fits(middle) {
+                low = middle;
+            } else {
+                high = middle;
+            }
+        }
+        low
+    }
+
+    /// Filled outline of each of `segments`, in the same order, for drawing at `scale`. Ends
+    /// which touch another segment are joined, and the rest are capped. Dashes have butt ends
+    /// inside the stroke, so caps never close the gaps of a pattern.
+    pub fn outlines(&self, segments: &[Segment], scale: f32) -> Vec<StrokeOutline> {
+        let pieces: Vec<Vec<Segment>> = segments
+            .iter()
+            .map(|&segment| self.apply(&[segment]))
+            .collect();
+        // Whether each segment draws right up to its start and its end, rather than stopping
+        // short in a gap of its dash pattern.
+        let drawn_ends: Vec<(bool, bool)> = segments
+            .iter()
+            .zip(&pieces)
+            .map(|(segment, pieces)| {
+                (
+                    pieces
+                        .first()
+                        .is_some_and(|piece| same_point(piece.start, segment.start)),
+                    pieces
+                        .last()
+                        .is_some_and(|piece| same_point(piece.end, segment.end)),
+                )
+            })
+            .collect();
+        let is_joined = |index: usize, point: GlyphPoint| {
+            segments
+                .iter()
+                .enumerate()
+                .any(|(other_index, other)| other_index != index && touches(other, point))
+        };
+
+        let mut joins: Vec<Vec<OutlineShape>> = vec![Vec::new(); segments.len()];
+        if self.stroke.pen == Pen::Uniform {
+            let half_width = self.stroke.width(scale) / scale / 2.0;
+            let mut done: Vec<GlyphPoint> = Vec::new();
+            for (index, segment) in segments.iter().enumerate() {
+                for point in [segment.start, segment.end] {
+                    if !is_joined(index, point) || done.iter().any(|&seen| same_point(seen, point))
+                    {
+                        continue;
+                    }
+                    done.push(point);
+                    if let Some((first, second, shape)) =
+                        self.stroke
+                            .join_shape(segments, &drawn_ends, point, half_width)
+                    {
+                        joins[first].push(shape.clone());
+                        joins[second].push(shape);
+                    }
+                }
+            }
+        }
+
+        segments
+            .iter()
+            .zip(pieces)
+            .zip(joins)
+            .enumerate()
+            .map(|(index, ((segment, pieces), joins))| {
+                let finish = |point: GlyphPoint| {
+                    if is_joined(index, point) {
+                        PieceEnd::Joined
+                    } else {
+                        PieceEnd::Capped
+                    }
+                };
+                let last = pieces.len().saturating_sub(1);
+                let mut outline = StrokeOutline {
+                    kind: segment.kind,
+                    body: Vec::new(),
+                    overlays: joins,
+                };
+                for (piece_index, piece) in pieces.iter().enumerate() {
+                    let start = if piece_index == 0 && same_point(piece.start, segment.start) {
+                        finish(segment.start)
+                    } else {
+                        PieceEnd::Butt
+                    };
+                    let end = if piece_index == last && same_point(piece.end, segment.end) {
+                        finish(segment.end)
+                    } else {
+                        PieceEnd::Butt
+                    };
+                    self.stroke
+                        .piece_shapes(piece, scale, start, end, &mut outline);
+                }
+                outline
+            })
+            .collect()
+    }
+}
+
+impl Default for GlyphStyle {
+    fn default() -> Self {
+        Self::new(LIGHT_CISTERCIAN_NUMERAL_COLOURS)
+    }
+}
+
+/// How the free ends of strokes are finished.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Hash, serde::Deserialize, serde::Serialize,
+)]
+pub enum LineCap {
+    /// Square, flush with the end of the stroke.
+    #[default]
+    Butt,
+
+    /// Half a disc past the end of the stroke.
+    Round,
+
+    /// Square, half the stroke width past the end of the stroke.
+    Square,
+}
+
+/// How the ends of strokes which meet, such as the stem and a 1 stroke, are joined.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Hash, serde::Deserialize, serde::Serialize,
+)]
+pub enum LineJoin {
+    /// Sharp corner.
+    #[default]
+    Miter,
+    Round,
+}
+
+/// What draws the strokes.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Hash, serde::Deserialize, serde::Serialize,
+)]
+pub enum Pen {
+    /// Even width in every direction.
+    #[default]
+    Uniform,
+
+    /// Broad nib held at a fixed angle, like the medieval sources: strokes across the nib are
+    /// broad and strokes along it are hairlines. Caps and joins do not apply, since the nib
+    /// shapes the ends.
+    Manuscript,
+}
+
+/// Shape and weight of glyph strokes.
+#[derive(Clone, Copy, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct StrokeStyle {
+    pub cap: LineCap,
+    pub join: LineJoin,
+
+    /// Multiplies the usual stroke width for the scale, from [`glyph::stroke_width`].
+    pub thickness: f32,
+    pub pen: Pen,
+}
+
+impl StrokeStyle {
+    pub const DEFAULT: Self = Self {
+        cap: LineCap::Butt,
+        join: LineJoin::Miter,
+        thickness: 1.0,
+        pen: Pen::Uniform,
+    };
+
+    pub const THICKNESS_RANGE: core::ops::RangeInclusive<f32> = 0.5..=3.0;
+
+    /// Angle of a manuscript nib, anticlockwise from horizontal.
+    const NIB_ANGLE: f32 = 40.0;
+
+    /// Longest a miter may reach from the joint, relative to the stroke width, before the
+    /// corner is bevelled instead, as in SVG.
+    const MITER_LIMIT: f32 = 4.0;
+
+    /// Width of a manuscript nib, relative to the uniform stroke width.
+    const NIB_WIDTH: f32 = 2.0;
+
+    /// Width of the thinnest manuscript stroke, relative to the uniform stroke width.
+    const HAIRLINE: f32 = 0.25;
+
+    /// Sharpest angle, in degrees, at which strokes of a glyph meet: a diagonal against the stem
+    /// or a horizontal stroke.
+    const SHARPEST_JOINT: f32 = 45.0;
+
+    /// Stroke width, in pixels, at `scale`.
+    pub fn width(&self, scale: f32) -> f32 {
+        glyph::stroke_width(scale) * self.thickness
+    }
+
+    /// Furthest, in glyph units, the ink reaches past the ends of the segments at `scale`.
+    pub fn overhang(&self, scale: f32) -> f32 {
+        let width = self.width(scale) / scale;
+        match self.pen {
+            Pen::Uniform => {
+                // A square cap's corner on a diagonal stroke reaches furthest of the caps.
+                let cap = match self.cap {
+                    LineCap::Butt | LineCap::Round => 1.0,
+                    LineCap::Square => core::f32::consts::SQRT_2,
+                };
+                let join = match self.join {
+                    LineJoin::Round => 1.0,
+                    LineJoin::Miter => (1.0 / (Self::SHARPEST_JOINT.to_radians() / 2.0).sin())
+                        .min(Self::MITER_LIMIT),
+                };
+                width / 2.0 * f32::max(cap, join)
+            }
+            Pen::Manuscript => width * Self::NIB_WIDTH / 2.0,
+        }
+    }
+
+    /// Adds the shapes which draw a single `piece` of a stroke, with its ends finished as
+    /// `start_finish` and `end_finish`, to `outline`.
+    fn piece_shapes(
+        &self,
+        piece: &Segment,
+        scale: f32,
+        start_finish: PieceEnd,
+        end_finish: PieceEnd,
+        outline: &mut StrokeOutline,
+    ) {
+        let Segment { start, end, .. } = *piece;
+        let length = (end.x - start.x).hypot(end.y - start.y);
+        if length <= f32::EPSILON {
+            return;
+        }
+        let direction = GlyphPoint::new((end.x - start.x) / length, (end.y - start.y) / length);
+        let width = self.width(scale) / scale;
+
+        match self.pen {
+            Pen::Uniform => {
+                let half_width = width / 2.0;
+                // Joins are drawn once for each joint, by `join_shape`.
+                let mut extension = |point: GlyphPoint, finish: PieceEnd| match (finish, self.cap) {
+                    (PieceEnd::Capped, LineCap::Square) => half_width,
+                    (PieceEnd::Capped, LineCap::Round) => {
+                        outline.overlays.push(OutlineShape::Disc {
+                            centre: point,
+                            radius: half_width,
+                        });
+                        0.0
+                    }
+                    (PieceEnd::Capped, LineCap::Butt) | (PieceEnd::Joined | PieceEnd::Butt, _) => {
+                        0.0
+                    }
+                };
+                let start_extension = extension(start, start_finish);
+                let end_extension = extension(end, end_finish);
+                let normal = GlyphPoint::new(-direction.y, direction.x);
+                outline.body.extend(OutlineShape::quad(
+                    offset(start, direction, -start_extension),
+                    offset(end, direction, end_extension),
+                    scaled(normal, half_width),
+                ));
+            }
+            Pen::Manuscript => {
+                let angle = Self::NIB_ANGLE.to_radians();
+                // Up and to the right, with y growing downwards.
+                let nib = scaled(
+                    GlyphPoint::new(angle.cos(), -angle.sin()),
+                    width * Self::NIB_WIDTH / 2.0,
+                );
+                let hairline = (width * Self::HAIRLINE).max(1.0 / scale) / 2.0;
+                let normal = GlyphPoint::new(-direction.y, direction.x);
+                // The wider of the two shapes is the body of the stroke.
+                let mut shapes: Vec<OutlineShape> = OutlineShape::quad(start, end, nib)
+                    .into_iter()
+                    .chain(OutlineShape::quad(start, end, scaled(normal, hairline)))
+                    .collect();
+                shapes.sort_by(|first, second| second.area().total_cmp(&first.area()));
+                let mut shapes = shapes.into_iter();
+                outline.body.extend(shapes.next());
+                outline.overlays.extend(shapes);
+            }
+        }
+    }
+}
+
+impl StrokeStyle {
+    /// Fill for the outside corner where strokes meet at `point`, with the indices of the two
+    /// segments either side of it, or `None` when the strokes leave no gap to fill. Segments
+    /// which run through `point` count in both directions, so a stroke ending on the side of
+    /// another needs no join. Joints a dash pattern leaves open are not filled.
+    fn join_shape(
+        &self,
+        segments: &[Segment],
+        drawn_ends: &[(bool, bool)],
+        point: GlyphPoint,
+        half_width: f32,
+    ) -> Option<(usize, usize, OutlineShape)> {
+        // Direction of each stroke away from `point`, with the segment it belongs to.
+        let mut directions: Vec<(f32, GlyphPoint, usize)> = Vec::new();
+        for (index, segment) in segments.iter().enumerate() {
+            let (start_drawn, end_drawn) = drawn_ends[index];
+            let Some(along) = unit(segment.start, segment.end) else {
+                continue;
+            };
+            let back = scaled(along, -1.0);
+            if same_point(segment.start, point) {
+                if !start_drawn {
+                    return None;
+                }
+                directions.push((along.y.atan2(along.x), along, index));
+            } else if same_point(segment.end, point) {
+                if !end_drawn {
+                    return None;
+                }
+                directions.push((back.y.atan2(back.x), back, index));
+            } else if touches(segment, point) {
+                directions.push((along.y.atan2(along.x), along, index));
+                directions.push((back.y.atan2(back.x), back, index));
+            }
+        }
+        if directions.len() < 2 {
+            return None;
+        }
+        directions.sort_by(|first, second| first.0.total_cmp(&second.0));
+
+        // The outside of the corner is the one gap between neighbouring strokes wider than a
+        // half turn.
+        let count = directions.len();
+        let (first, second) = (0..count)
+            .map(|position| (directions[position], directions[(position + 1) % count]))
+            .find(|(first, second)| {
+                let gap = (second.0 - first.0).rem_euclid(core::f32::consts::TAU);
+                gap > core::f32::consts::PI + 1e-3
+            })?;
+        let (_, first_direction, first_index) = first;
+        let (_, second_direction, second_index) = second;
+
+        let shape = match self.join {
+            LineJoin::Round => OutlineShape::Disc {
+                centre: point,
+                radius: half_width,
+            },
+            LineJoin::Miter => {
+                // Sides of the two strokes which face into the gap.
+                let first_side = GlyphPoint::new(-first_direction.y, first_direction.x);
+                let second_side = GlyphPoint::new(second_direction.y, -second_direction.x);
+                let cosine = first_side.x * second_side.x + first_side.y * second_side.y;
+                let first_corner = offset(point, first_side, half_width);
+                let second_corner = offset(point, second_side, half_width);
+                // A miter reaches sqrt(2 / (1 + cosine)) half widths from the joint.
+                let points = if 1.0 + cosine < 2.0 / (Self::MITER_LIMIT * Self::MITER_LIMIT) {
+                    vec![point, first_corner, second_corner]
+                } else {
+                    let tip = scaled(
+                        GlyphPoint::new(first_side.x + second_side.x, first_side.y + second_side.y),
+                        half_width / (1.0 + cosine),
+                    );
+                    vec![point, first_corner, offset(point, tip, 1.0), second_corner]
+                };
+                OutlineShape::polygon(points)?
+            }
+        };
+        Some((first_index, second_index, shape))
+    }
+}
+
+impl Default for StrokeStyle {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// How one end of a piece of stroke is finished.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum PieceEnd {
+    /// Free end of a stroke, finished with the cap.
+    Capped,
+
+    /// Where the stroke meets another, finished with the join.
+    Joined,
+
+    /// End of a dash inside the stroke, always square and flush.
+    Butt,
+}
+
+/// Filled shape, in glyph units, making up part of a stroke.
+#[derive(Clone, Debug, PartialEq)]
+pub enum OutlineShape {
+    /// Convex polygon, with its points clockwise on screen, where y grows downwards.
+    Polygon(Vec<GlyphPoint>),
+    Disc {
+        centre: GlyphPoint,
+        radius: f32,
+    },
+}
+
+impl OutlineShape {
+    /// Parallelogram swept by moving the line from `start - across` to `start + across` along
+    /// to `end`, or `None` when it has no area.
+    fn quad(start: GlyphPoint, end: GlyphPoint, across: GlyphPoint) -> Option<Self> {
+        Self::polygon(vec![
+            offset(start, across, 1.0),
+            offset(end, across, 1.0),
+            offset(end, across, -1.0),
+            offset(start, across, -1.0),
+        ])
+    }
+
+    /// Area, in square glyph units.
+    fn area(&self) -> f32 {
+        match self {
+            Self::Polygon(points) => signed_area(points).abs() / 2.0,
+            Self::Disc { radius, .. } => core::f32::consts::PI * radius * radius,
+        }
+    }
+
+    /// Convex polygon through `points`, in either order, or `None` when it has no area.
+    fn polygon(mut points: Vec<GlyphPoint>) -> Option<Self> {
+        let area = signed_area(&points);
+        if area.abs() <= f32::EPSILON {
+            return None;
+        }
+        if area < 0.0 {
+            points.reverse();
+        }
+        Some(Self::Polygon(points))
+    }
+}
+
+/// Everything to fill for one segment, drawn in the colour for `kind`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StrokeOutline {
+    pub kind: StrokeKind,
+
+    /// Shapes which never overlap one another: the main run of each piece of the stroke.
+    pub body: Vec<OutlineShape>,
+
+    /// Shapes which overlap the body, such as round ends, join fills and the hairline under a
+    /// manuscript stroke. A renderer which blends each shape on its own can leave them out of a
+    /// translucent stroke, where the overlaps would show as darker seams.
+    pub overlays: Vec<OutlineShape>,
+}
+
+impl StrokeOutline {
+    /// Every shape to fill, body first.
+    pub fn shapes(&self) -> impl Iterator<Item = &OutlineShape> {
+        self.body.iter().chain(&self.overlays)
+    }
+}
+
+/// Alternating drawn and skipped lengths, in glyph units, for strokes of `kind`, starting at the
+/// stem end of the stroke. Empty for a solid stroke.
+pub const fn dash_pattern(kind: StrokeKind) -> &'static [f32] {
+    match kind {
+        StrokeKind::Stem | StrokeKind::One | StrokeKind::Bracket => &[],
+        StrokeKind::Two => &[4.0, 2.0],
+        StrokeKind::Three => &[1.5, 1.5],
+        StrokeKind::Four => &[4.0, 1.5, 1.5, 1.5],
+        StrokeKind::Six => &[2.0, 2.0],
+    }
+}
+
+/// The drawn pieces of `segment` under its kind's [`dash_pattern`].
+fn dashes(segment: Segment) -> Vec<Segment> {
+    let pattern = dash_pattern(segment.kind);
+    let (delta_x, delta_y) = (
+        segment.end.x - segment.start.x,
+        segment.end.y - segment.start.y,
+    );
+    let length = delta_x.hypot(delta_y);
+    if pattern.is_empty() || length <= f32::EPSILON {
+        return vec![segment];
+    }
+
+    let point = |distance: f32| {
+        let fraction = distance / length;
+        GlyphPoint::new(
+            segment.start.x + fraction * delta_x,
+            segment.start.y + fraction * delta_y,
+        )
+    };
+    let mut result = Vec::new();
+    let mut distance = 0.0;
+    for (index, &step) in pattern.iter().cycle().enumerate() {
+        if distance >= length {
+            break;
+        }
+        let next = (distance + step).min(length);
+        if index % 2 == 0 {
+            result.push(Segment {
+                start: point(distance),
+                end: point(next),
+                ..segment
+            });
+        }
+        distance = next;
+    }
+    result
+}
+
+fn same_point(first: GlyphPoint, second: GlyphPoint) -> bool {
+    (first.x - second.x).abs() < 1e-3 && (first.y - second.y).abs() < 1e-3
+}
+
+/// Whether `point` lies on `segment`, at an end or part way along.
+fn touches(segment: &Segment, point: GlyphPoint) -> bool {
+    let Some(along) = unit(segment.start, segment.end) else {
+        return same_point(segment.start, point);
+    };
+    let (from_x, from_y) = (point.x - segment.start.x, point.y - segment.start.y);
+    let distance = from_x * along.x + from_y * along.y;
+    let length = (segment.end.x - segment.start.x).hypot(segment.end.y - segment.start.y);
+    (-1e-3..=length + 1e-3).contains(&distance)
+        && (from_x * along.y - from_y * along.x).abs() < 1e-3
+}
+
+/// Unit vector from `from` towards `to`, or `None` when they are the same point.
+fn unit(from: GlyphPoint, to: GlyphPoint) -> Option<GlyphPoint> {
+    let length = (to.x - from.x).hypot(to.y - from.y);
+    (length > f32::EPSILON)
+        .then(|| GlyphPoint::new((to.x - from.x) / length, (to.y - from.y) / length))
+}
+
+fn scaled(vector: GlyphPoint, factor: f32) -> GlyphPoint {
+    GlyphPoint::new(vector.x * factor, vector.y * factor)
+}
+
+/// `point` moved `factor` times `vector`.
+fn offset(point: GlyphPoint, vector: GlyphPoint, factor: f32) -> GlyphPoint {
+    GlyphPoint::new(point.x + factor * vector.x, point.y + factor * vector.y)
+}
+
+/// Twice the signed area of `points`, positive when they run clockwise on screen.
+fn signed_area(points: &[GlyphPoint]) -> f32 {
+    points
+        .iter()
+        .zip(points.iter().cycle().skip(1))
+        .map(|(first, second)| first.x * second.y - second.x * first.y)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        glyph::{Quadrant, ARM_LENGTH},
+        CistercianNumeral,
+    };
+
+    fn style(cap: LineCap, join: LineJoin) -> GlyphStyle {
+        GlyphStyle {
+            stroke: StrokeStyle {
+                cap,
+                join,
+                ..StrokeStyle::DEFAULT
+            },
+            ..GlyphStyle::default()
+        }
+    }
+
+    fn numeral_segments(value: u32) -> Vec<Segment> {
+        glyph::segments(
+            CistercianNumeral::try_from(value).unwrap(),
+            Orientation::Vertical,
+        )
+    }
+
+    fn polygons(shapes: &[OutlineShape]) -> Vec<&[GlyphPoint]> {
+        shapes
+            .iter()
+            .filter_map(|shape| match shape {
+                OutlineShape::Polygon(points) => Some(points.as_slice()),
+                OutlineShape::Disc { .. } => None,
+            })
+            .collect()
+    }
+
+    fn discs(shapes: &[OutlineShape]) -> Vec<GlyphPoint> {
+        shapes
+            .iter()
+            .filter_map(|shape| match shape {
+                OutlineShape::Disc { centre, .. } => Some(*centre),
+                OutlineShape::Polygon(_) => None,
+            })
+            .collect()
+    }
+
+    /// Smallest and largest y of `points`.
+    fn y_range(points: &[GlyphPoint]) -> (f32, f32) {
+        points
+            .iter()
+            .fold((f32::INFINITY, f32::NEG_INFINITY), |(low, high), point| {
+                (low.min(point.y), high.max(point.y))
+            })
+    }
+
+    fn close(first: f32, second: f32) -> bool {
+        (first - second).abs() < 1e-3
+    }
+
+    #[test]
+    fn caps_free_ends() {
+        // At scale 1.0 the stroke is 2 units wide, so caps reach 1 unit past the ends.
+        let stem = [Segment::stem()];
+        for (cap, reach, disc_count) in [
+            (LineCap::Butt, 16.0, 0),
+            (LineCap::Square, 17.0, 0),
+            (LineCap::Round, 16.0, 2),
+        ] {
+            let outlines = style(cap, LineJoin::Miter).outlines(&stem, 1.0);
+            let quads = polygons(&outlines[0].body);
+            assert_eq!(quads.len(), 1, "{cap:?}");
+            let (top, bottom) = y_range(quads[0]);
+            assert!(close(top, -reach) && close(bottom, reach), "{cap:?}");
+            assert_eq!(discs(&outlines[0].overlays).len(), disc_count, "{cap:?}");
+        }
+    }
+
+    #[test]
+    fn t_junction_needs_no_join() {
+        // The stem ends where units 1 and tens 1 meet in a straight line, so nothing is left
+        // open to fill.
+        for join in [LineJoin::Miter, LineJoin::Round] {
+            for outline in style(LineCap::Butt, join).outlines(&numeral_segments(11), 1.0) {
+                assert_eq!(outline.body.len(), 1, "{join:?} {}", outline.kind);
+                assert!(outline.overlays.is_empty(), "{join:?} {}", outline.kind);
+            }
+        }
+    }
+
+    #[test]
+    fn mitres_stem_and_diagonal() {
+        // The 3 stroke leaves the top of the stem at 45°, so the miter reaches
+        // 1 / sin(22.5°) half widths from the joint, straight up the stem's outer edge.
+        let outlines = style(LineCap::Butt, LineJoin::Miter).outlines(&numeral_segments(3), 1.0);
+        let tip = GlyphPoint::new(-1.0, -STEM_HALF_LENGTH - core::f32::consts::SQRT_2 - 1.0);
+        for outline in &outlines {
+            let join = polygons(&outline.overlays)[0];
+            assert_eq!(join.len(), 4, "{}", outline.kind);
+            assert!(
+                join.iter().any(|&point| same_point(point, tip)),
+                "{}: {join:?}",
+                outline.kind
+            );
+        }
+    }
+
+    #[test]
+    fn bevels_past_miter_limit() {
+        // About 11° between the strokes, so a miter would reach over 10 half widths.
+        let joint = GlyphPoint::new(0.0, -STEM_HALF_LENGTH);
+        let sharp = Segment {
+            quadrant: Some(Quadrant::Units),
+            kind: StrokeKind::One,
+            start: joint,
+            end: GlyphPoint::new(2.0, -STEM_HALF_LENGTH + ARM_LENGTH),
+        };
+        let outlines =
+            style(LineCap::Butt, LineJoin::Miter).outlines(&[Segment::stem(), sharp], 1.0);
+        let join = polygons(&outlines[0].overlays)[0];
+        assert_eq!(join.len(), 3);
+        assert!(join
+            .iter()
+            .all(|point| (point.x - joint.x).hypot(point.y - joint.y) <= 1.0 + 1e-3));
+    }
+
+    #[test]
+    fn dashes_cap_only_real_ends() {
+        // The 6 stroke runs from (10, -16) to (10, -6) as dashes of 2 with gaps of 2, and
+        // touches no other stroke.
+        let style = GlyphStyle {
+            patterned: true,
+            ..style(LineCap::Square, LineJoin::Miter)
+        };
+        let outlines = style.outlines(&numeral_segments(6), 1.0);
+        let six = &outlines[1];
+        assert_eq!(six.kind, StrokeKind::Six);
+        let mut ranges: Vec<(f32, f32)> = polygons(&six.body).into_iter().map(y_range).collect();
+        ranges.sort_by(|first, second| first.0.total_cmp(&second.0));
+        let expected = [(-17.0, -14.0), (-12.0, -10.0), (-8.0, -5.0)];
+        assert_eq!(ranges.len(), expected.len());
+        for (range, expected) in ranges.iter().zip(expected) {
+            assert!(
+                close(range.0, expected.0) && close(range.1, expected.1),
+                "{ranges:?}"
+            );
+        }
+
+        let round = GlyphStyle {
+            stroke: StrokeStyle {
+                cap: LineCap::Round,
+                ..style.stroke
+            },
+            ..style
+        };
+        let outlines = round.outlines(&numeral_segments(6), 1.0);
+        let mut ends = discs(&outlines[1].overlays);
+        ends.sort_by(|first, second| first.y.total_cmp(&second.y));
+        assert_eq!(
+            ends,
+            [GlyphPoint::new(10.0, -16.0), GlyphPoint::new(10.0, -6.0)]
+        );
+    }
+
+    #[test]
+    fn manuscript_body_is_wider_quad() {
+        let style = GlyphStyle {
+            stroke: StrokeStyle {
+                pen: Pen::Manuscript,
+                ..StrokeStyle::default()
+            },
+            ..GlyphStyle::default()
+        };
+        for outline in style.outlines(&numeral_segments(9_999), 4.0) {
+            assert_eq!(outline.body.len(), 1, "{}", outline.kind);
+            assert_eq!(outline.overlays.len(), 1, "{}", outline.kind);
+            assert!(outline.body[0].area() >= outline.overlays[0].area());
+        }
+    }
+
+    #[test]
+    fn cell_holds_every_outline() {
+        let mut strokes = Vec::new();
+        for cap in [LineCap::Butt, LineCap::Round, LineCap::Square] {
+            for join in [LineJoin::Miter, LineJoin::Round] {
+                for pen in [Pen::Uniform, Pen::Manuscript] {
+                    for thickness in [0.5, 1.0, 3.0] {
+                        strokes.push(StrokeStyle {
+                            cap,
+                            join,
+                            thickness,
+                            pen,
+                        });
+                    }
+                }
+            }
+        }
+
+        for stroke in strokes {
+            let style = GlyphStyle {
+                stroke,
+                ..GlyphStyle::default()
+            };
+            for scale in [0.5, 1.0, 4.0] {
+                let half_cell = style.cell_size(scale) / 2.0 + 1e-3;
+                let inside = |point: &GlyphPoint, radius: f32| {
+                    point.x.abs() + radius <= half_cell && point.y.abs() + radius <= half_cell
+                };
+                // Every digit in every quadrant.
+                for digit in 0..=9 {
+                    for outline in style.outlines(&numeral_segments(digit * 1_111), scale) {
+                        for shape in outline.shapes() {
+                            let fits = match shape {
+                                OutlineShape::Polygon(points) => {
+                                    points.iter().all(|point| inside(point, 0.0))
+                                }
+                                OutlineShape::Disc { centre, radius } => inside(centre, *radius),
+                            };
+                            assert!(fits, "{stroke:?} at scale {scale}: {shape:?}");
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn fitted_cells_fit() {
+        let style = GlyphStyle {
+            stroke: StrokeStyle {
+                cap: LineCap::Square,
+                thickness: 3.0,
+                ..StrokeStyle::DEFAULT
+            },
+            ..GlyphStyle::default()
+        };
+        for side in [20.0, 34.0, 100.0, 400.0] {
+            let scale = style.fit_scale((side, 2.0 * side), (1.0, 2.0), (0.0, 0.0));
+            assert!(scale * style.cell_size(scale) <= side + 1e-3, "{side}");
+        }
+    }
+}
@@ -0,0 +1,304 @@
+//! Standalone SVG documents drawn from the glyph stroke geometry.
+
+use crate::{
+    glyph::{self, CompoundGlyph, Grouping, Segment, CHART_COLUMNS, GLYPH_GAP},
+    palette,
+    style::{GlyphStyle, OutlineShape, StrokeOutline},
+    transition::{self, AnimatedSegment, TransitionStyle},
+    CistercianNumeral, CompoundNumeral,
+};
+use chrono::NaiveTime;
+use core::{fmt::Write, ops::Range};
+use ecolor::Color32;
+
+/// SVG document with a single glyph for `numeral`, drawn at `scale`.
+pub fn numeral_svg(numeral: CistercianNumeral, scale: f32, style: &GlyphStyle) -> String {
+    let size = scale * style.cell_size(scale);
+    let mut body = String::new();
+    write_glyph(&mut body, numeral, size / 2.0, size / 2.0, scale, style);
+    document(size, size, &body)
+}
+
+/// SVG document with the glyphs for a number of any size, grouped by `grouping`.
+pub fn compound_svg(
+    number: CompoundNumeral,
+    grouping: Grouping,
+    scale: f32,
+    style: &GlyphStyle,
+) -> String {
+    let CompoundGlyph {
+        segments,
+        width,
+        height,
+    } = glyph::compound_glyph(number, grouping, style.orientation);
+    let margin = 2.0 * style.margin(scale);
+    let (width, height) = (scale * (width + margin), scale * (height + margin));
+    let mut body = String::new();
+    write_segments(
+        &mut body,
+        &segments,
+        width / 2.0,
+        height / 2.0,
+        scale,
+        style,
+    );
+    document(width, height, &body)
+}
+
+/// SVG document showing `time` as the clock does: an hours-minutes glyph followed by a seconds
+/// glyph.
+pub fn time_svg(time: NaiveTime, scale: f32, style: &GlyphStyle) -> String {
+    let (hours_minutes, seconds) = glyph::time_numerals(time, false);
+
+    let size = scale * style.cell_size(scale);
+    let mut body = String::new();
+    write_glyph(
+        &mut body,
+        hours_minutes,
+        size / 2.0,
+        size / 2.0,
+        scale,
+        style,
+    );
+    write_glyph(
+        &mut body,
+        seconds,
+        size + GLYPH_GAP + size / 2.0,
+        size / 2.0,
+        scale,
+        style,
+    );
+    document(2.0 * size + GLYPH_GAP, size, &body)
+}
+
+/// SVG reference chart of every number in `numbers`, ten to a row, each captioned with its
+/// Arabic value. Numbers which do not fit in a single glyph are skipped.
+pub fn chart_svg(numbers: Range<u32>, scale: f32, style: &GlyphStyle) -> String {
+    let size = scale * style.cell_size(scale);
+    let caption_size = 12.0 * scale.max(1.0);
+    let cell_width = size + GLYPH_GAP;
+    let cell_height = caption_size + size + 2.0 * GLYPH_GAP;
+
+    let numerals: Vec<CistercianNumeral> = numbers
+        .filter_map(|number| CistercianNumeral::try_from(number).ok())
+        .collect();
+    let rows = numerals.len().div_ceil(CHART_COLUMNS);
+    let columns = numerals.len().min(CHART_COLUMNS);
+
+    let mut body = String::new();
+    for (index, numeral) in numerals.into_iter().enumerate() {
+        let left = (index % CHART_COLUMNS) as f32 * cell_width;
+        let top = (index / CHART_COLUMNS) as f32 * cell_height;
+        let centre_x = left + size / 2.0;
+        let _ = writeln!(
+            body,
+            r#"<text x="{centre_x}" y="{}" font-family="sans-serif" font-size="{caption_size}" text-anchor="middle" fill="{}">{}</text>"#,
+            top + caption_size,
+            hex(style.colours.colour_0),
+            format_args!("{numeral:#}"),
+        );
+        write_glyph(
+            &mut body,
+            numeral,
+            centre_x,
+            top + caption_size + GLYPH_GAP + size / 2.0,
+            scale,
+            style,
+        );
+    }
+
+    document(
+        columns as f32 * cell_width,
+        rows as f32 * cell_height,
+        &body,
+    )
+}
+
+/// One SVG document per frame of the animated change from `from` to `to`, for presentations
+/// that step through how the strokes change. See [`transition::transition_frames`].
+pub fn transition_svgs(
+    from: CistercianNumeral,
+    to: CistercianNumeral,
+    transition: TransitionStyle,
+    frames: usize,
+    scale: f32,
+    style: &GlyphStyle,
+) -> Vec<String> {
+    let size = scale * style.cell_size(scale);
+    transition::transition_frames(from, to, transition, style.orientation, frames)
+        .into_iter()
+        .map(|segments| {
+            let mut body = String::new();
+            write_animated_segments(&mut body, &segments, size / 2.0, size / 2.0, scale, style);
+            document(size, size, &body)
+        })
+        .collect()
+}
+
+fn document(width: f32, height: f32, body: &str) -> String {
+    format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">
+{body}</svg>
+"#
+    )
+}
+
+fn write_glyph(
+    out: &mut String,
+    numeral: CistercianNumeral,
+    centre_x: f32,
+    centre_y: f32,
+    scale: f32,
+    style: &GlyphStyle,
+) {
+    write_segments(
+        out,
+        &glyph::segments(numeral, style.orientation),
+        centre_x,
+        centre_y,
+        scale,
+        style,
+    );
+}
+
+fn write_segments(
+    out: &mut String,
+    segments: &[Segment],
+    centre_x: f32,
+    centre_y: f32,
+    scale: f32,
+    style: &GlyphStyle,
+) {
+    for outline in style.outlines(segments, scale) {
+        let colour = style.colours.stroke_colour(outline.kind);
+        write_shapes(out, &outline, centre_x, centre_y, scale, colour);
+    }
+}
+
+fn write_animated_segments(
+    out: &mut String,
+    segments: &[AnimatedSegment],
+    centre_x: f32,
+    centre_y: f32,
+    scale: f32,
+    style: &GlyphStyle,
+) {
+    let plain: Vec<Segment> = segments.iter().map(|animated| animated.segment).collect();
+    for (outline, animated) in style.outlines(&plain, scale).iter().zip(segments) {
+        let colour =
+            palette::with_opacity(style.colours.stroke_colour(outline.kind), animated.opacity);
+        write_shapes(out, outline, centre_x, centre_y, scale, colour);
+    }
+}
+
+/// Writes the shapes of one stroke as a group, so a translucent stroke is drawn at one opacity
+/// however its shapes overlap.
+fn write_shapes(
+    out: &mut String,
+    outline: &StrokeOutline,
+    centre_x: f32,
+    centre_y: f32,
+    scale: f32,
+    colour: Color32,
+) {
+    if outline.shapes().next().is_none() {
+        return;
+    }
+    let _ = write!(out, r#"<g fill="{}""#, hex(colour));
+    if colour.a() < u8::MAX {
+        let _ = write!(out, r#" opacity="{}""#, f32::from(colour.a()) / 255.0);
+    }
+    let _ = writeln!(out, ">");
+    for shape in outline.shapes() {
+        match shape {
+            OutlineShape::Polygon(points) => {
+                let points: Vec<String> = points
+                    .iter()
+                    .map(|point| {
+                        format!(
+                            "{},{}",
+                            centre_x + scale * point.x,
+                            centre_y + scale * point.y
+                        )
+                    })
+                    .collect();
+                let _ = writeln!(out, r#"<polygon points="{}"/>"#, points.join(" "));
+            }
+            OutlineShape::Disc { centre, radius } => {
+                let _ = writeln!(
+                    out,
+                    r#"<circle cx="{}" cy="{}" r="{}"/>"#,
+                    centre_x + scale * centre.x,
+                    centre_y + scale * centre.y,
+                    scale * radius,
+                );
+            }
+        }
+    }
+    let _ = writeln!(out, "</g>");
+}
+
+/// `#rrggbb` hex form of `colour`, ignoring alpha.
+fn hex(colour: Color32) -> String {
+    let [red, green, blue, _] = colour.to_srgba_unmultiplied();
+    format!("#{red:02x}{green:02x}{blue:02x}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Width and height of an SVG document.
+    fn size(svg: &str) -> (f32, f32) {
+        let attribute = |name: &str| {
+            let start = svg.find(&format!(r#" {name}=""#)).unwrap() + name.len() + 3;
+            let end = start + svg[start..].find('"').unwrap();
+            svg[start..end].parse().unwrap()
+        };
+        (attribute("width"), attribute("height"))
+    }
+
+    #[test]
+    fn numeral_fills_one_cell() {
+        let style = GlyphStyle::default();
+        let cell = 2.0 * style.cell_size(2.0);
+        let svg = numeral_svg(CistercianNumeral::try_from(1_984_u32).unwrap(), 2.0, &style);
+        assert_eq!(size(&svg), (cell, cell));
+    }
+
+    #[test]
+    fn time_has_two_cells() {
+        let style = GlyphStyle::default();
+        let cell = style.cell_size(1.0);
+        let time = NaiveTime::from_hms_opt(12, 34, 56).unwrap();
+        assert_eq!(
+            size(&time_svg(time, 1.0, &style)),
+            (2.0 * cell + GLYPH_GAP, cell)
+        );
+    }
+
+    #[test]
+    fn chart_wraps_after_ten_columns() {
+        let style = GlyphStyle::default();
+        let cell = style.cell_size(1.0);
+        let svg = chart_svg(0..15, 1.0, &style);
+        assert_eq!(
+            size(&svg),
+            (
+                10.0 * (cell + GLYPH_GAP),
+                2.0 * (12.0 + cell + 2.0 * GLYPH_GAP)
+            )
+        );
+        assert_eq!(svg.matches("<text").count(), 15);
+    }
+
+    #[test]
+    fn chart_skips_numbers_out_of_range() {
+        let style = GlyphStyle::default();
+        let cell = style.cell_size(1.0);
+        let svg = chart_svg(9_998..10_002, 1.0, &style);
+        assert_eq!(svg.matches("<text").count(), 2);
+        assert!(svg.contains(">9,998<") && svg.contains(">9,999<"));
+        assert_eq!(size(&svg).0, 2.0 * (cell + GLYPH_GAP));
+    }
+}
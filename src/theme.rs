@@ -0,0 +1,184 @@
+//! Look of the clock app: egui visuals for each theme and the text styles it uses, kept as data
+//! so apps embedding the widgets can merge them into their own style or change them first.
+
+use egui::{
+    epaint::Shadow,
+    style::{HandleShape, NumericColorSpace, Selection, TextCursorStyle, Widgets},
+    Color32, Context, CornerRadius,
+    FontFamily::Proportional,
+    FontId, Stroke,
+    TextStyle::{self, Body, Button, Heading, Monospace, Small},
+    Visuals,
+};
+use std::collections::BTreeMap;
+
+/// Visuals and fonts for the app. Apply it once, with [`ClockTheme::apply`], rather than every
+/// frame: egui keeps a style for each theme and switches between them itself.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClockTheme {
+    pub dark: Visuals,
+    pub light: Visuals,
+
+    /// Fonts for the standard text styles. Merged into the existing styles, so styles missing
+    /// here keep their current font.
+    pub text_styles: BTreeMap<TextStyle, FontId>,
+
+    /// Font for the digital time, used only there rather than registered as a text style.
+    pub clock_font: FontId,
+}
+
+impl Default for ClockTheme {
+    fn default() -> Self {
+        Self {
+            dark: dark_visuals(),
+            light: light_visuals(),
+            text_styles: [
+                (Heading, FontId::new(30.0, Proportional)),
+                (Body, FontId::new(18.0, Proportional)),
+                (Monospace, FontId::new(14.0, Proportional)),
+                (Button, FontId::new(14.0, Proportional)),
+                (Small, FontId::new(10.0, Proportional)),
+            ]
+            .into(),
+            clock_font: FontId::new(64.0, Proportional),
+        }
+    }
+}
+
+impl ClockTheme {
+    /// Visuals for the dark or light theme.
+    pub fn visuals(&self, dark_mode: bool) -> &Visuals {
+        if dark_mode {
+            &self.dark
+        } else {
+            &self.light
+        }
+    }
+
+    /// Sets the visuals for both of `ctx`'s themes, and merges in the text styles. Call it once,
+    /// when the app starts, and again only if the theme itself changes.
+    pub fn apply(&self, ctx: &Context) {
+        ctx.set_visuals_of(egui::Theme::Dark, self.dark.clone());
+        ctx.set_visuals_of(egui::Theme::Light, self.light.clone());
+        ctx.all_styles_mut(|style| {
+            style.text_styles.extend(self.text_styles.clone());
+        });
+    }
+}
+
+fn dark_visuals() -> Visuals {
+    Visuals {
+        dark_mode: true,
+        override_text_color: Some(Color32::from_gray(252)),
+        widgets: Widgets::default(),
+        selection: Selection::default(),
+        hyperlink_color: Color32::from_rgb(90, 170, 255),
+        faint_bg_color: Color32::from_additive_luminance(5), // visible, but barely so
+        extreme_bg_color: Color32::from_gray(10),            // e.g. TextEdit background
+        code_bg_color: Color32::from_gray(64),
+        warn_fg_color: Color32::from_rgb(255, 143, 0), // orange
+        error_fg_color: Color32::from_rgb(255, 0, 0),  // red
+
+        window_corner_radius: CornerRadius::same(6),
+        window_shadow: Shadow {
+            offset: [10, 20],
+            blur: 15,
+            spread: 0,
+            color: Color32::from_black_alpha(96),
+        },
+        window_fill: Color32::from_rgb(23, 18, 25),
+        window_stroke: Stroke::new(1.0, Color32::from_gray(60)),
+        window_highlight_topmost: true,
+
+        menu_corner_radius: CornerRadius::same(6),
+
+        panel_fill: Color32::from_rgb(23, 18, 25),
+
+        popup_shadow: Shadow {
+            offset: [6, 10],
+            blur: 8,
+            spread: 0,
+            color: Color32::from_black_alpha(96),
+        },
+        resize_corner_size: 12.0,
+        text_cursor: Default::default(),
+        clip_rect_margin: 3.0, // should be at least half the size of the widest frame stroke + max WidgetVisuals::expansion
+        button_frame: true,
+        collapsing_header_frame: false,
+        indent_has_left_vline: true,
+
+        striped: false,
+
+        slider_trailing_fill: false,
+
+        handle_shape: HandleShape::Rect { aspect_ratio: 1.0 },
+
+        interact_cursor: None,
+
+        image_loading_spinners: true,
+
+        numeric_color_space: NumericColorSpace::GammaByte,
+    }
+}
+
+fn light_visuals() -> Visuals {
+    Visuals {
+        dark_mode: false,
+        override_text_color: Some(Color32::from_rgb(4, 3, 15)),
+        widgets: Widgets::light(),
+        selection: Selection::default(),
+        hyperlink_color: Color32::from_rgb(0, 155, 255),
+        faint_bg_color: Color32::from_additive_luminance(5), // visible, but barely so
+        extreme_bg_color: Color32::from_gray(255),           // e.g. TextEdit background
+        code_bg_color: Color32::from_gray(230),
+        warn_fg_color: Color32::from_rgb(255, 100, 0), // slightly orange red. it's difficult to find a warning color that pops on bright background.
+        error_fg_color: Color32::from_rgb(255, 0, 0),  // red
+
+        window_shadow: Shadow {
+            offset: [10, 20],
+            blur: 15,
+            spread: 0,
+            color: Color32::from_black_alpha(25),
+        },
+        window_fill: Color32::from_gray(255),
+        window_stroke: Stroke::new(1.0, Color32::from_gray(190)),
+
+        panel_fill: Color32::from_gray(255),
+
+        popup_shadow: Shadow {
+            offset: [6, 10],
+            blur: 8,
+            spread: 0,
+            color: Color32::from_black_alpha(25),
+        },
+        text_cursor: TextCursorStyle {
+            stroke: Stroke::new(2.0, Color32::from_rgb(0, 83, 125)),
+            ..Default::default()
+        },
+        ..Visuals::dark()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::palette::{PalettePreset, MIN_GRAPHIC_CONTRAST};
+
+    #[test]
+    fn presets_contrast_with_their_theme() {
+        let theme = ClockTheme::default();
+        for preset in PalettePreset::ALL {
+            for dark_mode in [true, false] {
+                let contrast = preset
+                    .colours(dark_mode)
+                    .min_contrast(theme.visuals(dark_mode).panel_fill);
+                assert!(
+                    contrast >= MIN_GRAPHIC_CONTRAST,
+                    "{} in {} mode only reaches {contrast:.1}:1",
+                    preset.name(),
+                    if dark_mode { "dark" } else { "light" },
+                );
+            }
+        }
+    }
+}
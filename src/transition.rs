@@ -0,0 +1,178 @@
+//! Animated change from one glyph to another, shared by the app and the exporters.
+//!
+//! Strokes the two glyphs share stay put. New strokes grow out from the point where they meet
+//! the rest of the glyph, while strokes which leave either retract the same way or fade out.
+
+use crate::{
+    glyph::{self, GlyphPoint, Orientation, Segment},
+    CistercianNumeral,
+};
+
+/// How strokes which are not in the target glyph leave.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Hash, serde::Deserialize, serde::Serialize,
+)]
+pub enum TransitionStyle {
+    /// Shrink back towards the stem, the reverse of how new strokes grow.
+    #[default]
+    Retract,
+
+    /// Stay at full length and fade out.
+    Fade,
+}
+
+/// A segment part way through a transition.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AnimatedSegment {
+    pub segment: Segment,
+
+    /// 0.0 for invisible, up to 1.0 for the stroke colour at full strength.
+    pub opacity: f32,
+}
+
+/// Ease-in-out curve applied to linear progress, so strokes start and finish moving gently.
+pub fn ease(progress: f32) -> f32 {
+    let t = progress.clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
+
+/// Segments, in `orientation`, for the change from `from` to `to` at `progress`, from 0.0 at
+/// `from` to 1.0 at `to`. `progress` is linear in time; [`ease`] is applied here.
+pub fn transition_segments(
+    from: CistercianNumeral,
+    to: CistercianNumeral,
+    style: TransitionStyle,
+    orientation: Orientation,
+    progress: f32,
+) -> Vec<AnimatedSegment> {
+    let t = ease(progress);
+    let (from, to) = (
+        glyph::segments(from, orientation),
+        glyph::segments(to, orientation),
+    );
+
+    let mut result: Vec<AnimatedSegment> = to
+        .iter()
+        .map(|&segment| AnimatedSegment {
+            segment: if from.contains(&segment) {
+                segment
+            } else {
+                partial(segment, t)
+            },
+            opacity: 1.0,
+        })
+        .collect();
+    result.extend(from.iter().filter(|segment| !to.contains(segment)).map(
+        |&segment| match style {
+            TransitionStyle::Retract => AnimatedSegment {
+                segment: partial(segment, 1.0 - t),
+                opacity: 1.0,
+            },
+            TransitionStyle::Fade => AnimatedSegment {
+                segment,
+                opacity: 1.0 - t,
+            },
+        },
+    ));
+    result.retain(|animated| {
+        animated.opacity > 0.0 && animated.segment.start != animated.segment.end
+    });
+    result
+}
+
+/// Evenly spaced frames of the change from `from` to `to`, including both end glyphs. Fewer
+/// than two frames gives just the end glyph.
+pub fn transition_frames(
+    from: CistercianNumeral,
+    to: CistercianNumeral,
+    style: TransitionStyle,
+    orientation: Orientation,
+    frames: usize,
+) -> Vec<Vec<AnimatedSegment>> {
+    if frames < 2 {
+        return vec![transition_segments(from, to, style, orientation, 1.0)];
+    }
+    let last = (frames - 1) as f32;
+    (0..frames)
+        .map(|frame| transition_segments(from, to, style, orientation, frame as f32 / last))
+        .collect()
+}
+
+/// First `fraction` of `segment`, measured from its start.
+fn partial(segment: Segment, fraction: f32) -> Segment {
+    let Segment { start, end, .. } = segment;
+    Segment {
+        end: GlyphPoint::new(
+            start.x + fraction * (end.x - start.x),
+            start.y + fraction * (end.y - start.y),
+        ),
+        ..segment
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numeral(value: u32) -> CistercianNumeral {
+        CistercianNumeral::try_from(value).unwrap()
+    }
+
+    fn length(segment: &Segment) -> f32 {
+        (segment.end.x - segment.start.x).hypot(segment.end.y - segment.start.y)
+    }
+
+    /// Whether `animated` is exactly `expected`, in any order, at full opacity.
+    fn same_segments(animated: &[AnimatedSegment], expected: &[Segment]) -> bool {
+        animated.len() == expected.len()
+            && animated
+                .iter()
+                .all(|animated| animated.opacity == 1.0 && expected.contains(&animated.segment))
+    }
+
+    #[test]
+    fn ends_are_the_glyphs() {
+        for style in [TransitionStyle::Retract, TransitionStyle::Fade] {
+            for (from, to) in [(1, 2), (9, 1_234), (0, 9_999)] {
+                let (from, to) = (numeral(from), numeral(to));
+                let start = transition_segments(from, to, style, Orientation::Vertical, 0.0);
+                let end = transition_segments(from, to, style, Orientation::Vertical, 1.0);
+                assert!(same_segments(
+                    &start,
+                    &glyph::segments(from, Orientation::Vertical)
+                ));
+                assert!(same_segments(
+                    &end,
+                    &glyph::segments(to, Orientation::Vertical)
+                ));
+            }
+        }
+    }
+
+    #[test]
+    fn midway_grows_and_fades() {
+        let segments = transition_segments(
+            numeral(1),
+            numeral(2),
+            TransitionStyle::Fade,
+            Orientation::Vertical,
+            0.5,
+        );
+        let stroke = |kind| {
+            segments
+                .iter()
+                .find(|animated| {
+                    animated.segment.kind == kind && animated.segment.quadrant.is_some()
+                })
+                .unwrap()
+        };
+
+        let new = stroke(glyph::StrokeKind::Two);
+        assert_eq!(new.opacity, 1.0);
+        assert!((length(&new.segment) - glyph::ARM_LENGTH / 2.0).abs() < 1e-4);
+
+        let leaving = stroke(glyph::StrokeKind::One);
+        assert_eq!(leaving.opacity, 0.5);
+        assert!((length(&leaving.segment) - glyph::ARM_LENGTH).abs() < 1e-4);
+    }
+}
@@ -0,0 +1,439 @@
+//! egui widgets for drawing Cistercian numerals inside any egui app, without the clock app's
+//! window, menus or style overrides.
+
+use crate::{
+    glyph::{self, GlyphPoint, Orientation, Segment, GLYPH_GAP},
+    palette::{self, Colours, DARK_CISTERCIAN_NUMERAL_COLOURS, LIGHT_CISTERCIAN_NUMERAL_COLOURS},
+    style::{GlyphStyle, OutlineShape, StrokeOutline},
+    transition::AnimatedSegment,
+    CistercianNumeral,
+};
+use chrono::{Local, NaiveTime, Timelike};
+use core::time::Duration;
+use egui::{
+    vec2, Align2, Color32, Painter, Pos2, Rect, Response, Sense, Shape, Stroke, TextStyle, Ui,
+    Vec2, Widget, WidgetInfo, WidgetType,
+};
+
+/// Gap between the caption and the glyph of a [`Numeral`], in points.
+const CAPTION_GAP: f32 = 4.0;
+
+/// Width of the dot marking a 12-hour time as before or after noon, in glyph units.
+pub(crate) const MERIDIEM_DOT_WIDTH: f32 = 6.0;
+
+/// A single Cistercian glyph, optionally captioned with its Arabic value underneath.
+#[derive(Clone, Copy, Debug)]
+#[must_use = "Add the numeral to a `Ui` with `ui.add(numeral)`"]
+pub struct Numeral {
+    numeral: CistercianNumeral,
+    scale: f32,
+    style: Option<GlyphStyle>,
+    colours: Option<Colours>,
+    caption: bool,
+}
+
+impl Numeral {
+    /// `numeral` at scale 1.0, in the default palette for the current theme, without a caption.
+    pub fn new(numeral: CistercianNumeral) -> Self {
+        Self {
+            numeral,
+            scale: 1.0,
+            style: None,
+            colours: None,
+            caption: false,
+        }
+    }
+
+    /// Scale of the glyph, where 1.0 draws a 34 point glyph.
+    pub fn scale(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Stroke colours, replacing the default palette for the current theme.
+    pub fn palette(mut self, colours: Colours) -> Self {
+        self.colours = Some(colours);
+        self
+    }
+
+    /// Full drawing style. Colours from [`Self::palette`] still take precedence.
+    pub fn style(mut self, style: GlyphStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// Show the Arabic value under the glyph.
+    pub fn caption(mut self, caption: bool) -> Self {
+        self.caption = caption;
+        self
+    }
+}
+
+impl Widget for Numeral {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let Self {
+            numeral,
+            scale,
+            style,
+            colours,
+            caption,
+        } = self;
+        let style = resolve_style(ui, style, colours);
+        let glyph_size = Vec2::splat(scale * style.cell_size(scale));
+        let galley = caption.then(|| {
+            ui.painter().layout_no_wrap(
+                format!("{numeral:#}"),
+                TextStyle::Body.resolve(ui.style()),
+                ui.visuals().text_color(),
+            )
+        });
+        let caption_height = galley
+            .as_ref()
+            .map_or(0.0, |galley| galley.size().y + CAPTION_GAP);
+        let caption_width = galley.as_ref().map_or(0.0, |galley| galley.size().x);
+        let size = vec2(
+            glyph_size.x.max(caption_width),
+            glyph_size.y + caption_height,
+        );
+
+        let (rect, response) = ui.allocate_exact_size(size, Sense::hover());
+        response.widget_info(|| {
+            WidgetInfo::labeled(WidgetType::Label, ui.is_enabled(), format!("{numeral:#}"))
+        });
+        if ui.is_rect_visible(rect) {
+            let painter = ui.painter_at(rect);
+            let glyph_rect = Rect::from_center_size(
+                Pos2::new(rect.center().x, rect.top() + glyph_size.y / 2.0),
+                glyph_size,
+            );
+            paint_glyph(&painter, glyph_rect.center(), scale, &style, numeral);
+            if let Some(galley) = galley {
+                let position = Align2::CENTER_BOTTOM
+                    .anchor_size(rect.center_bottom(), galley.size())
+                    .min;
+                painter.galley(position, galley, ui.visuals().text_color());
+            }
+        }
+        response
+    }
+}
+
+/// Clock showing the time as an hours-minutes glyph, optionally followed by a seconds glyph.
+/// It fills the space it is given, scaling the glyphs to fit, so size it with
+/// [`Ui::add_sized`] or place it in a sized layout.
+#[derive(Clone, Copy, Debug)]
+#[must_use = "Add the clock to a `Ui` with `ui.add(clock)`"]
+pub struct CistercianClock {
+    time: Option<NaiveTime>,
+    show_seconds: bool,
+    twelve_hour: bool,
+    style: Option<GlyphStyle>,
+    colours: Option<Colours>,
+}
+
+impl Default for CistercianClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CistercianClock {
+    /// Clock showing the local time in 24-hour form, with seconds.
+    pub fn new() -> Self {
+        Self {
+            time: None,
+            show_seconds: true,
+            twelve_hour: false,
+            style: None,
+            colours: None,
+        }
+    }
+
+    /// Show `time` rather than the local time, for example the time in another zone. The clock
+    /// only repaints itself while showing the local time.
+    pub fn time(mut self, time: NaiveTime) -> Self {
+        self.time = Some(time);
+        self
+    }
+
+    pub fn show_seconds(mut self, show_seconds: bool) -> Self {
+        self.show_seconds = show_seconds;
+        self
+    }
+
+    /// Count hours 1–12 rather than 0–23, with a dot marking the time as before or after noon.
+    /// See [`paint_meridiem_dot`].
+    pub fn twelve_hour(mut self, twelve_hour: bool) -> Self {
+        self.twelve_hour = twelve_hour;
+        self
+    }
+
+    /// Stroke colours, replacing the default palette for the current theme.
+    pub fn palette(mut self, colours: Colours) -> Self {
+        self.colours = Some(colours);
+        self
+    }
+
+    /// Full drawing style. Colours from [`Self::palette`] still take precedence.
+    pub fn style(mut self, style: GlyphStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+}
+
+impl Widget for CistercianClock {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let Self {
+            time,
+            show_seconds,
+            twelve_hour,
+            style,
+            colours,
+        } = self;
+        let style = resolve_style(ui, style, colours);
+        let time = time.unwrap_or_else(|| {
+            ui.ctx().request_repaint_after(Duration::from_secs(1));
+            Local::now().time()
+        });
+        let (hours_minutes, seconds) = glyph::time_numerals(time, twelve_hour);
+        let is_pm = time.hour() >= 12;
+        let dot_room = if twelve_hour {
+            meridiem_dot_room(style.orientation)
+        } else {
+            Vec2::ZERO
+        };
+        let mut items = vec![ClockItem::Glyph(hours_minutes)];
+        if dot_room.x > 0.0 {
+            items.push(ClockItem::MeridiemDot(dot_room.x));
+        }
+        if show_seconds {
+            items.push(ClockItem::Glyph(seconds));
+        }
+
+        let (rect, response) = ui.allocate_exact_size(ui.available_size(), Sense::hover());
+        response.widget_info(|| {
+            let format = if twelve_hour {
+                "%-I:%M:%S %p"
+            } else {
+                "%H:%M:%S"
+            };
+            WidgetInfo::labeled(
+                WidgetType::Label,
+                ui.is_enabled(),
+                time.format(format).to_string(),
+            )
+        });
+        if !ui.is_rect_visible(rect) {
+            return response;
+        }
+
+        // Items sit side by side, with a gap between them, and a dot under the first glyph
+        // rather than after it takes a row of its own, all in glyph units.
+        let width = |cell: f32| {
+            items.iter().map(|item| item.width(cell)).sum::<f32>()
+                + (items.len() - 1) as f32 * GLYPH_GAP
+        };
+        let dot_row = if dot_room.y > 0.0 {
+            GLYPH_GAP + dot_room.y
+        } else {
+            0.0
+        };
+        let height = |cell: f32| cell + dot_row;
+        let glyphs = items
+            .iter()
+            .filter(|item| matches!(item, ClockItem::Glyph(_)))
+            .count() as f32;
+        let scale = style.fit_scale(
+            (rect.width(), rect.height()),
+            (glyphs, 1.0),
+            // Everything but the glyph cells themselves.
+            (width(0.0), dot_row),
+        );
+        let cell = style.cell_size(scale);
+        if scale <= 0.0 {
+            return response;
+        }
+        let painter = ui.painter_at(rect);
+        let mut left = rect.center().x - scale * width(cell) / 2.0;
+        let top = rect.center().y - scale * height(cell) / 2.0;
+        for &item in &items {
+            let centre = Pos2::new(
+                left + scale * item.width(cell) / 2.0,
+                top + scale * cell / 2.0,
+            );
+            match item {
+                ClockItem::Glyph(numeral) => paint_glyph(&painter, centre, scale, &style, numeral),
+                ClockItem::MeridiemDot(_) => {
+                    paint_meridiem_dot(&painter, centre, scale, &style, is_pm);
+                }
+            }
+            left += scale * (item.width(cell) + GLYPH_GAP);
+        }
+        if dot_room.y > 0.0 {
+            let centre = Pos2::new(
+                rect.center().x - scale * (width(cell) - cell) / 2.0,
+                top + scale * (cell + GLYPH_GAP + dot_room.y / 2.0),
+            );
+            paint_meridiem_dot(&painter, centre, scale, &style, is_pm);
+        }
+        response
+    }
+}
+
+/// Something drawn in a row of a [`CistercianClock`].
+#[derive(Clone, Copy)]
+enum ClockItem {
+    Glyph(CistercianNumeral),
+
+    /// Meridiem dot beside the glyph before it, this many glyph units wide.
+    MeridiemDot(f32),
+}
+
+impl ClockItem {
+    /// Width in glyph units, when glyphs take cells `cell` units across.
+    fn width(self, cell: f32) -> f32 {
+        match self {
+            Self::Glyph(_) => cell,
+            Self::MeridiemDot(width) => width,
+        }
+    }
+}
+
+/// `style`, or the default for the current theme, with `colours` in place of its own.
+fn resolve_style(ui: &Ui, style: Option<GlyphStyle>, colours: Option<Colours>) -> GlyphStyle {
+    let mut style = style.unwrap_or_else(|| {
+        GlyphStyle::new(if ui.visuals().dark_mode {
+            DARK_CISTERCIAN_NUMERAL_COLOURS
+        } else {
+            LIGHT_CISTERCIAN_NUMERAL_COLOURS
+        })
+    });
+    if let Some(colours) = colours {
+        style.colours = colours;
+    }
+    style
+}
+
+/// Draws the glyph for `numeral` with its centre at `centre`.
+pub fn paint_glyph(
+    painter: &Painter,
+    centre: Pos2,
+    scale: f32,
+    style: &GlyphStyle,
+    numeral: CistercianNumeral,
+) {
+    paint_segments(
+        painter,
+        centre,
+        scale,
+        style,
+        &glyph::segments(numeral, style.orientation),
+    );
+}
+
+/// Draws `segments`, in glyph units, with the glyph origin at `centre`.
+pub fn paint_segments(
+    painter: &Painter,
+    centre: Pos2,
+    scale: f32,
+    style: &GlyphStyle,
+    segments: &[Segment],
+) {
+    for outline in style.outlines(segments, scale) {
+        let colour = style.colours.stroke_colour(outline.kind);
+        paint_outline(painter, centre, scale, &outline, colour);
+    }
+}
+
+/// Draws `segments` part way through a transition, with the glyph origin at `centre`.
+pub fn paint_animated_segments(
+    painter: &Painter,
+    centre: Pos2,
+    scale: f32,
+    style: &GlyphStyle,
+    segments: &[AnimatedSegment],
+) {
+    let plain: Vec<Segment> = segments.iter().map(|animated| animated.segment).collect();
+    for (outline, animated) in style.outlines(&plain, scale).iter().zip(segments) {
+        let colour =
+            palette::with_opacity(style.colours.stroke_colour(outline.kind), animated.opacity);
+        // egui blends each shape separately, so a translucent stroke leaves out the shapes
+        // overlapping its body rather than showing darker seams where they meet.
+        paint_shapes(painter, centre, scale, &outline.body, colour);
+        if animated.opacity >= 1.0 {
+            paint_shapes(painter, centre, scale, &outline.overlays, colour);
+        }
+    }
+}
+
+/// Room the meridiem dot takes next to a glyph drawn in `orientation`, across and down, in glyph
+/// units: a column after the glyph for an upright stem, or a row under it for a horizontal one.
+pub(crate) fn meridiem_dot_room(orientation: Orientation) -> Vec2 {
+    match orientation {
+        Orientation::Vertical => vec2(MERIDIEM_DOT_WIDTH, 0.0),
+        Orientation::Horizontal => vec2(0.0, MERIDIEM_DOT_WIDTH),
+    }
+}
+
+/// Draws the dot marking a 12-hour time as before or after noon, in the room from
+/// [`meridiem_dot_room`] centred on `centre`. The dot lines up with the end of the neighbouring
+/// glyph's stem which is the top in the upright form before noon, and with the other end after
+/// noon.
+pub fn paint_meridiem_dot(
+    painter: &Painter,
+    centre: Pos2,
+    scale: f32,
+    style: &GlyphStyle,
+    is_pm: bool,
+) {
+    let stem_end = if is_pm {
+        glyph::STEM_HALF_LENGTH
+    } else {
+        -glyph::STEM_HALF_LENGTH
+    };
+    let offset = style.orientation.orient(GlyphPoint::new(0.0, stem_end));
+    painter.circle_filled(
+        centre + scale * vec2(offset.x, offset.y),
+        scale * 2.0,
+        style.colours.colour_0,
+    );
+}
+
+/// Fills every shape of `outline`, in glyph units, with the glyph origin at `centre`.
+pub(crate) fn paint_outline(
+    painter: &Painter,
+    centre: Pos2,
+    scale: f32,
+    outline: &StrokeOutline,
+    colour: Color32,
+) {
+    paint_shapes(painter, centre, scale, &outline.body, colour);
+    paint_shapes(painter, centre, scale, &outline.overlays, colour);
+}
+
+fn paint_shapes(
+    painter: &Painter,
+    centre: Pos2,
+    scale: f32,
+    shapes: &[OutlineShape],
+    colour: Color32,
+) {
+    let to_screen = |point: GlyphPoint| centre + scale * vec2(point.x, point.y);
+    for shape in shapes {
+        match shape {
+            OutlineShape::Polygon(points) => {
+                painter.add(Shape::convex_polygon(
+                    points.iter().copied().map(to_screen).collect(),
+                    colour,
+                    Stroke::NONE,
+                ));
+            }
+            OutlineShape::Disc {
+                centre: disc_centre,
+                radius,
+            } => {
+                painter.circle_filled(to_screen(*disc_centre), scale * radius, colour);
+            }
+        }
+    }
+}